@@ -0,0 +1,11 @@
+/// Returns the number of cells of an array with the given dimensions or None
+/// if the number does not fit into usize.
+pub(crate) fn try_len(dims: &[usize]) -> Option<usize> {
+    dims.iter().try_fold(1usize, |len, d| len.checked_mul(*d))
+}
+
+/// Returns the number of cells of an array with the given dimensions. Panics
+/// if the number does not fit into usize, before any memory is allocated.
+pub(crate) fn checked_len(dims: &[usize]) -> usize {
+    try_len(dims).expect("Array dimensions overflow usize")
+}
@@ -0,0 +1,44 @@
+/// Error returned by the fallible methods of `Array2d` and `Array3d`. `V` is
+/// the vector type positions and dimensions are expressed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrayError<V> {
+    /// A position lies outside of an array. Holds the offending position
+    /// along with the dimensions of the array.
+    OutOfBounds { pos: V, dims: V },
+    /// A buffer does not hold exactly as many items as the requested
+    /// dimensions require.
+    SizeMismatch { expected: usize, got: usize },
+    /// An iterator yields more items than the requested dimensions require.
+    /// The iterator is not drained, so the actual number is unknown.
+    TooManyItems { expected: usize },
+    /// Arrays that have to share their dimensions do not.
+    DimMismatch { expected: V, got: V },
+    /// An array was requested without any extent along one of its axes.
+    ZeroDimension,
+    /// The number of cells of the requested dimensions does not fit into
+    /// usize.
+    DimensionOverflow,
+}
+
+impl<V: std::fmt::Display> std::fmt::Display for ArrayError<V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArrayError::OutOfBounds { pos, dims } => {
+                write!(f, "position {} is outside of an array with dimensions {}", pos, dims)
+            }
+            ArrayError::SizeMismatch { expected, got } => {
+                write!(f, "expected {} items but got {}", expected, got)
+            }
+            ArrayError::TooManyItems { expected } => {
+                write!(f, "expected {} items but got more", expected)
+            }
+            ArrayError::DimMismatch { expected, got } => {
+                write!(f, "expected dimensions {} but got {}", expected, got)
+            }
+            ArrayError::ZeroDimension => write!(f, "array dimensions must not be zero"),
+            ArrayError::DimensionOverflow => write!(f, "array dimensions overflow usize"),
+        }
+    }
+}
+
+impl<V: std::fmt::Debug + std::fmt::Display> std::error::Error for ArrayError<V> {}
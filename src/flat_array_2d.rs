@@ -2,8 +2,13 @@ use std::ops::{Index, IndexMut};
 
 use bevy::prelude::*;
 
+use crate::dimensions::{checked_len, try_len};
+use crate::error::ArrayError;
+use crate::flat_array_3d::Array3d;
+
 /// Get the array index for the given position. This is the inverse operation
-/// to get_2d_from_1d.
+/// to get_2d_from_1d. The data is laid out row by row, so `x` is the
+/// horizontal axis bounded by `width` and `y` the vertical axis.
 /// 
 /// # Examples
 /// ```
@@ -12,7 +17,20 @@ use bevy::prelude::*;
 /// assert_eq!(i, 3);
 /// ```
 pub fn get_1d_from_2d(width: usize, x: usize, y: usize) -> usize {
-    width * x + y
+    width * y + x
+}
+
+/// Get the array index for the given position like get_1d_from_2d, but
+/// returns None instead of overflowing on huge dimensions.
+///
+/// # Example
+/// ```
+/// use bevy_flat_arrays::prelude::tools::try_get_1d_from_2d;
+/// assert_eq!(try_get_1d_from_2d(2, 1, 1), Some(3));
+/// assert_eq!(try_get_1d_from_2d(usize::MAX, 0, 2), None);
+/// ```
+pub fn try_get_1d_from_2d(width: usize, x: usize, y: usize) -> Option<usize> {
+    width.checked_mul(y)?.checked_add(x)
 }
 
 /// Get the position from an index. This is the inverse operation
@@ -31,7 +49,7 @@ pub fn get_1d_from_2d(width: usize, x: usize, y: usize) -> usize {
 /// assert_eq!(y, y1);
 /// ```
 pub fn get_2d_from_1d(width: usize, i: usize) -> (usize, usize) {
-    (i / width, i % width)
+    (i % width, i / width)
 }
 
 /// Returns the array index for the given vector.
@@ -76,14 +94,15 @@ pub fn get_2d_from_1d_ivec2(width: usize, i: usize) -> IVec2 {
 /// of an inventory hud with its tiles arranged in a grid. If the
 /// layout of an 2d array matches the grid of this inventory we can
 /// calculate the index of a tile from its screen position using this
-/// function.
+/// function. The returned x is the column and y the row, matching the
+/// positions used by Array2d.
 /// ```
 /// use bevy::prelude::*;
-/// use bevy_flat_arrays::prelude::tools::quantize_to_grid;
+/// use bevy_flat_arrays::prelude::tools::map_to_grid_vec2;
 /// let v = Vec2 { x : 35.8277, y : 7.987278, };
 /// let grid_size = 4.0;
-/// let mapped = quantize_to_grid(v, grid_size);
-/// assert_eq!(Vec2 { x : 32.0, y : 4.0 }, mapped); 
+/// let mapped = map_to_grid_vec2(v, grid_size);
+/// assert_eq!(IVec2 { x : 8, y : 1 }, mapped); 
 /// 
 /// ```
 pub fn map_to_grid_vec2(v : Vec2, grid_size : f32) -> IVec2 {
@@ -95,6 +114,79 @@ pub fn map_to_grid_vec2(v : Vec2, grid_size : f32) -> IVec2 {
     }
 }
 
+/// Map a world vector to a cell on a grid with `dims` columns and rows.
+/// Returns None if the position lies outside of the grid, e.g. a click
+/// next to an inventory hud.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_flat_arrays::prelude::tools::map_to_grid_bounded;
+/// let dims = IVec2 { x : 4, y : 2 };
+/// assert_eq!(Some(IVec2 { x : 3, y : 1 }), map_to_grid_bounded(Vec2 { x : 200.0, y : 70.0 }, 64.0, dims));
+/// assert_eq!(None, map_to_grid_bounded(Vec2 { x : 260.0, y : 70.0 }, 64.0, dims));
+/// ```
+pub fn map_to_grid_bounded(v : Vec2, grid_size : f32, dims : IVec2) -> Option<IVec2> {
+    let mapped = (v / grid_size).floor().as_ivec2();
+    let inside = mapped.cmpge(IVec2::ZERO).all() && mapped.cmplt(dims).all();
+
+    inside.then_some(mapped)
+}
+
+/// Map a world vector to a cell on a grid whose first cell starts at
+/// `origin` instead of the world origin. Positions left of or below the
+/// origin map to negative cells.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_flat_arrays::prelude::tools::map_to_grid_vec2_offset;
+/// let origin = Vec2 { x : 100.0, y : 100.0 };
+/// let mapped = map_to_grid_vec2_offset(Vec2 { x : 99.0, y : 170.0 }, 64.0, origin);
+/// assert_eq!(IVec2 { x : -1, y : 1 }, mapped);
+/// ```
+pub fn map_to_grid_vec2_offset(v : Vec2, grid_size : f32, origin : Vec2) -> IVec2 {
+    map_to_grid_vec2(v - origin, grid_size)
+}
+
+/// Map a world vector to a cell on a grid with rectangular cells. Each axis
+/// is divided by its own cell size. Panics if a component of `cell` is zero.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_flat_arrays::prelude::tools::map_to_grid_vec2_nonuniform;
+/// let cell = Vec2 { x : 64.0, y : 32.0 };
+/// let mapped = map_to_grid_vec2_nonuniform(Vec2 { x : 70.0, y : 70.0 }, cell);
+/// assert_eq!(IVec2 { x : 1, y : 2 }, mapped);
+/// ```
+pub fn map_to_grid_vec2_nonuniform(v : Vec2, cell : Vec2) -> IVec2 {
+    assert!(cell.x != 0.0 && cell.y != 0.0, "Invalid cell size");
+
+    (v / cell).floor().as_ivec2()
+}
+
+/// Map a world vector to a cell on a predefined grid and return the offset
+/// of the vector inside of that cell as well. Each component of the offset
+/// lies in `[0, grid_size)`.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_flat_arrays::prelude::tools::map_to_grid_with_offset;
+/// let (cell, offset) = map_to_grid_with_offset(Vec2 { x : 70.0, y : 10.0 }, 64.0);
+/// assert_eq!(IVec2 { x : 1, y : 0 }, cell);
+/// assert_eq!(Vec2 { x : 6.0, y : 10.0 }, offset);
+/// ```
+pub fn map_to_grid_with_offset(v : Vec2, grid_size : f32) -> (IVec2, Vec2) {
+    let cell = map_to_grid_vec2(v, grid_size);
+    let offset = v - quantize_to_grid(v, grid_size);
+
+    (cell, offset)
+}
+
+/// Snap a world vector to the corner of the grid cell it falls into.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_flat_arrays::prelude::tools::quantize_to_grid;
+/// let v = Vec2 { x : 35.8277, y : 7.987278, };
+/// let grid_size = 4.0;
+/// let mapped = quantize_to_grid(v, grid_size);
+/// assert_eq!(Vec2 { x : 32.0, y : 4.0 }, mapped); 
+/// 
+/// ```
 pub fn quantize_to_grid(v : Vec2, grid_size : f32) -> Vec2 {
     let x = (v.x / grid_size).floor() * grid_size;
     let y = (v.y / grid_size).floor() * grid_size;
@@ -102,6 +194,39 @@ pub fn quantize_to_grid(v : Vec2, grid_size : f32) -> Vec2 {
     Vec2 { x, y, }
 }
 
+/// Returns the center of a cell in world units. Where quantize_to_grid
+/// returns the corner of a cell, this can be used to snap an object to the
+/// middle of a tile.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_flat_arrays::prelude::tools::grid_cell_center;
+/// let center = grid_cell_center(IVec2 { x : 1, y : 2 }, 64.0);
+/// assert_eq!(Vec2 { x : 96.0, y : 160.0 }, center);
+/// ```
+pub fn grid_cell_center(cell : IVec2, grid_size : f32) -> Vec2 {
+    (cell.as_vec2() + Vec2::splat(0.5)) * grid_size
+}
+
+/// Offsets of the orthogonal (von Neumann) neighbors of a cell.
+const NEIGHBORS_4: [IVec2; 4] = [
+    IVec2::new(0, -1),
+    IVec2::new(-1, 0),
+    IVec2::new(1, 0),
+    IVec2::new(0, 1),
+];
+
+/// Offsets of the orthogonal and diagonal (Moore) neighbors of a cell.
+const NEIGHBORS_8: [IVec2; 8] = [
+    IVec2::new(-1, -1),
+    IVec2::new(0, -1),
+    IVec2::new(1, -1),
+    IVec2::new(-1, 0),
+    IVec2::new(1, 0),
+    IVec2::new(-1, 1),
+    IVec2::new(0, 1),
+    IVec2::new(1, 1),
+];
+
 /// # Array2d
 /// 
 /// This array creates a 2 dimensional array that keeps its data in a cache friendly way.
@@ -109,6 +234,29 @@ pub fn quantize_to_grid(v : Vec2, grid_size : f32) -> Vec2 {
 /// indirections. This should result in an increase in performance when iterating
 /// over the data.
 /// 
+/// # Coordinates
+/// 
+/// Positions are given as `IVec2` where x is the column (bounded by `width`) and y
+/// is the row (bounded by `height`). The same convention is used by `get`, `set`,
+/// the iterators and the grid helpers like `map_to_grid_vec2`. Rows are stored one
+/// after another, so the cell at `(x, y)` lives at the flat index `y * width + x`.
+/// 
+/// # Stride
+/// 
+/// Rows may be padded to a `stride` that is larger than the width, e.g. to align
+/// them for an upload to the GPU. See `new_with_stride`. Positions and flat indices
+/// always refer to the logical cells, only `as_slice` and `as_mut_slice` expose the
+/// padded buffer where the cell at `(x, y)` lives at `y * stride + x`.
+/// 
+/// # Origin
+/// 
+/// An array created with `new_with_origin` covers the positions `[origin, origin + size)`.
+/// Every position taken or yielded by the array is relative to that origin, including
+/// the accessors, the iterators, rows and columns and the drawing and search helpers.
+/// Arrays derived from another one, e.g. by `map`, `sub_region` or `transpose`, are
+/// fresh arrays starting at zero, use `with_origin` to move them. Flat indices and the
+/// backing storage always start at zero.
+/// 
 /// # Traits and behaviour
 /// 
 /// Both a immutable and an mutable iterator are provided. However, both iterators
@@ -118,255 +266,1861 @@ pub fn quantize_to_grid(v : Vec2, grid_size : f32) -> Vec2 {
 /// no additional comnputation takes place.
 /// 
 /// The memory for the array is allocated when a new array is created and can be resized
-/// using the resize function. To make it easier to allocate memory, all types are required
-/// to implement the Default trait. 
-pub struct Array2d<T: std::default::Default> {
+/// using the resize function. Only the functions that have to create new cells, like `new`
+/// and `resize`, require the Default trait. An array of any other type can be built with
+/// `from_vec` or `from_fn`.
+/// 
+/// # Bevy
+/// 
+/// The array is a component, so it can be stored directly on an entity, e.g.
+/// `commands.spawn(Array2d::<Tile>::new(16, 16))`. This requires `T` to be
+/// `Send + Sync + 'static`.
+#[derive(Component, Reflect)]
+pub struct Array2d<T> {
     width: usize,
     height: usize,
+    stride: usize,
+    origin: IVec2,
     array: Vec<T>,
 }
 
-impl<T: std::default::Default> Array2d<T> {
+impl<T> Array2d<T> {
     /// Constructs a new array.
-    pub fn new(width: usize, height: usize) -> Self {
-        assert!(width > 0);
-        assert!(height > 0);
+    pub fn new(width: usize, height: usize) -> Self
+    where
+        T: std::default::Default,
+    {
+        let mut r: Vec<T> = Vec::new();
+        r.resize_with(checked_len(&[width, height]), || T::default());
+
+        Array2d {
+            width,
+            height,
+            stride: width,
+            origin: IVec2::ZERO,
+            array: r,
+        }
+    }
+
+    /// Constructs a new array with every cell set to a clone of `value`.
+    /// Unlike `new` this never calls `T::default`, which is useful when the
+    /// default value is expensive to create and every cell is overwritten
+    /// anyway.
+    pub fn new_uninit_filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Array2d {
+            width,
+            height,
+            stride: width,
+            origin: IVec2::ZERO,
+            array: vec![value; checked_len(&[width, height])],
+        }
+    }
+
+    /// Constructs a new array whose rows are padded to `stride` cells. The
+    /// padding is never visited by the accessors or iterators and its content
+    /// is unspecified. Panics if the stride is smaller than the width.
+    pub fn new_with_stride(width: usize, height: usize, stride: usize) -> Self
+    where
+        T: std::default::Default,
+    {
+        assert!(stride >= width, "Stride must not be smaller than the width");
         let mut r: Vec<T> = Vec::new();
-        r.resize_with(width * height, || T::default());
+        r.resize_with(checked_len(&[stride, height]), || T::default());
 
         Array2d {
             width,
             height,
+            stride,
+            origin: IVec2::ZERO,
             array: r,
         }
     }
 
+    /// Constructs a new array covering the positions `[min, min + size)`.
+    /// Every method of the array takes and yields positions in that range,
+    /// so a grid around a chunk origin can be addressed with negative
+    /// coordinates. Panics if `size` is negative.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_flat_arrays::prelude::*;
+    /// let mut array : Array2d<i32> = Array2d::new_with_origin(IVec2::new(-2, -2), IVec2::new(4, 4));
+    /// array.set(IVec2::new(-2, -1), 5);
+    /// assert_eq!(*array.get(IVec2::new(-2, -1)), 5);
+    /// assert!(!array.contains_point(IVec2::new(2, 0)));
+    /// ```
+    pub fn new_with_origin(min : IVec2, size : IVec2) -> Self
+    where
+        T: std::default::Default,
+    {
+        assert!(size.x >= 0 && size.y >= 0, "Invalid size");
+
+        let mut r = Array2d::new(size.x as usize, size.y as usize);
+        r.origin = min;
+        r
+    }
+
+    /// Constructs a new array that takes ownership of the given buffer. The
+    /// buffer is expected to be laid out row by row and has to hold exactly
+    /// width * height items.
+    pub fn from_vec(width: usize, height: usize, data: Vec<T>) -> Result<Self, ArrayError<IVec2>> {
+        let Some(expected) = try_len(&[width, height]) else {
+            return Err(ArrayError::DimensionOverflow);
+        };
+        if data.len() != expected {
+            return Err(ArrayError::SizeMismatch { expected, got: data.len() });
+        }
+
+        Ok(Array2d {
+            width,
+            height,
+            stride: width,
+            origin: IVec2::ZERO,
+            array: data,
+        })
+    }
+
+    /// Constructs a new array from the items of `iter` in flat order. The
+    /// iterator has to yield exactly width * height items. An iterator that
+    /// yields more is not drained any further, so unbounded iterators are
+    /// rejected right away.
+    pub fn from_iter_flat(width: usize, height: usize, iter: impl IntoIterator<Item = T>) -> Result<Self, ArrayError<IVec2>> {
+        let Some(expected) = try_len(&[width, height]) else {
+            return Err(ArrayError::DimensionOverflow);
+        };
+        let mut iter = iter.into_iter();
+        let data : Vec<T> = iter.by_ref().take(expected).collect();
+
+        if iter.next().is_some() {
+            return Err(ArrayError::TooManyItems { expected });
+        }
+
+        Array2d::from_vec(width, height, data)
+    }
+
+    /// Constructs a new array and initializes every cell by calling `f` with
+    /// the position of the cell. The cells are generated in flat order.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(IVec2) -> T) -> Self {
+        let array = (0..checked_len(&[width, height]))
+            .map(|i| f(get_2d_from_1d_ivec2(width, i)))
+            .collect();
+
+        Array2d {
+            width,
+            height,
+            stride: width,
+            origin: IVec2::ZERO,
+            array,
+        }
+    }
+
+    /// Constructs a new array and initializes every cell by calling `f` with
+    /// the coordinates of the cell normalized to `[0, 1]`, i.e.
+    /// `x / (width - 1)` and `y / (height - 1)`. A dimension of one always
+    /// yields a coordinate of 0.0.
+    pub fn from_normalized(width: usize, height: usize, f: impl Fn(f32, f32) -> T) -> Self {
+        let normalize = |value : i32, size : usize| -> f32 {
+            if size > 1 { value as f32 / (size - 1) as f32 } else { 0.0 }
+        };
+
+        Array2d::from_fn(width, height, |pos| f(normalize(pos.x, width), normalize(pos.y, height)))
+    }
 
     /// Get the value for the given position.
     pub fn get(&self, v : IVec2) -> &T {
-        let i = get_1d_from_2d_ivec2(self.width, v);
-        assert!(i < self.len(), "Invalid index");
+        let i = self.origin_index(v).expect("Invalid index");
         &self.array[i]
     }
 
     /// Get a mutable reference for the given position.
     pub fn get_mut(&mut self, v : IVec2) -> &mut T {
-        let i = get_1d_from_2d_ivec2(self.width, v);
-        assert!(i < self.len(), "Invalid index");
+        let i = self.origin_index(v).expect("Invalid index");
         &mut self.array[i]
     }
 
     /// Update the value for the given position.
     pub fn set(&mut self, v : IVec2, value : T) {
-        let i = get_1d_from_2d_ivec2(self.width, v);
-        assert!(i < self.len(), "Invalid index");
+        let i = self.origin_index(v).expect("Invalid index");
         self.array[i] = value;
     }
 
-    /// Resize this array to the given dimensions. Allocates 
-    /// the needed memory right away.
-    pub fn resize(&mut self, width : usize, heigth : usize) {
-        self.height = heigth;
-        self.width = width;
-        self.array.resize_with(width * heigth, || T::default());
+    /// Update the value for the given position. Returns an error instead of
+    /// panicking if the position lies outside of this array.
+    pub fn try_set(&mut self, v : IVec2, value : T) -> Result<(), ArrayError<IVec2>> {
+        let Some(i) = self.origin_index(v) else {
+            return Err(self.out_of_bounds(v));
+        };
+
+        self.array[i] = value;
+        Ok(())
     }
-    
-    /// Returns the number of items inside this array holds.
-    pub fn len(&self) -> usize {
-        self.width * self.height
+
+    /// Exchange the values of the two given positions.
+    pub fn swap(&mut self, a : IVec2, b : IVec2) {
+        let i = self.origin_index(a).expect("Invalid index");
+        let j = self.origin_index(b).expect("Invalid index");
+        self.array.swap(i, j);
     }
 
-    /// Implemented to silence the compiler. Always return false.
-    pub fn is_empty(&self) -> bool {
-        false
+    /// Get the value for the given position. Returns None if the position
+    /// lies outside of this array.
+    pub fn get_checked(&self, v : IVec2) -> Option<&T> {
+        self.origin_index(v).map(|i| &self.array[i])
     }
 
-    /// Creates a new immutable iterator.
-    pub fn iter(&self) -> Array2dIter<'_, T> {
-        Array2dIter {
-            items: &self.array,
-            cursor: 0,
-            max: self.len(),
-            width: self.width,
+    /// Get a mutable reference for the given position. Returns None if the
+    /// position lies outside of this array.
+    pub fn get_mut_checked(&mut self, v : IVec2) -> Option<&mut T> {
+        self.origin_index(v).map(|i| &mut self.array[i])
+    }
+
+    /// Get the value for the given flat index. Returns None if the index is
+    /// not smaller than `len`.
+    pub fn get_flat(&self, i : usize) -> Option<&T> {
+        if i >= self.len() {
+            return None;
         }
+
+        Some(&self.array[self.physical_index(i)])
     }
 
-    /// Creates a new mutable iterator.
-    fn iter_mut(&mut self) -> Array2dMutIter<'_, T> {
-        let len = self.len();
+    /// Get a mutable reference for the given flat index. Returns None if the
+    /// index is not smaller than `len`.
+    pub fn get_flat_mut(&mut self, i : usize) -> Option<&mut T> {
+        if i >= self.len() {
+            return None;
+        }
 
-        Array2dMutIter {
-            items: &mut self.array,
-            cursor: 0,
-            max: len,
-            width: self.width,
+        let i = self.physical_index(i);
+        Some(&mut self.array[i])
+    }
+
+    /// Get mutable references to several positions at once. Returns None if
+    /// any position lies outside of this array or two positions are equal.
+    pub fn get_many_mut<const N: usize>(&mut self, positions : [IVec2; N]) -> Option<[&mut T; N]> {
+        let mut indices = [0; N];
+        for (index, v) in indices.iter_mut().zip(positions) {
+            *index = self.origin_index(v)?;
         }
+
+        self.array.get_disjoint_mut(indices).ok()
     }
-}
 
-impl<T: std::default::Default> Index<usize> for Array2d<T> {
-    type Output = T;
+    /// Get the value for the given position, wrapping each coordinate around
+    /// the dimensions of this array. Positions before the origin count from
+    /// the end, so `origin - (1, 1)` is the cell at the bottom right.
+    ///
+    /// # Panics
+    /// Panics if this array is empty.
+    pub fn get_wrapped(&self, v : IVec2) -> &T {
+        self.local(self.wrap(v))
+    }
 
-    fn index(&self, index: usize) -> &Self::Output {
-        assert!(index < self.len());
-        &self.array[index]
+    /// Get a mutable reference for the given position, wrapping each
+    /// coordinate around the dimensions of this array.
+    ///
+    /// # Panics
+    /// Panics if this array is empty.
+    pub fn get_wrapped_mut(&mut self, v : IVec2) -> &mut T {
+        self.local_mut(self.wrap(v))
     }
-}
 
-impl<T: std::default::Default> IndexMut<usize> for Array2d<T> {
-    fn index_mut(&mut self, index: usize) -> &mut T {
-        assert!(index < self.len());
-        &mut self.array[index]
+    /// Update the value for the given position, wrapping each coordinate
+    /// around the dimensions of this array.
+    ///
+    /// # Panics
+    /// Panics if this array is empty.
+    pub fn set_wrapped(&mut self, v : IVec2, value : T) {
+        *self.local_mut(self.wrap(v)) = value;
     }
-}
 
-pub struct Array2dIter<'a, T: std::default::Default> {
-    items: &'a Vec<T>,
-    cursor: usize,
-    max: usize,
-    width: usize,
-}
+    /// Map any position onto this array using the euclidean remainder.
+    fn wrap(&self, v : IVec2) -> IVec2 {
+        assert!(!self.is_empty(), "Array is empty");
 
-impl<'a, T: std::default::Default> Iterator for Array2dIter<'a, T> {
-    type Item = (IVec2, &'a T);
+        let v = self.to_local(v);
+        IVec2 {
+            x: v.x.rem_euclid(self.width as i32),
+            y: v.y.rem_euclid(self.height as i32),
+        }
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let tmp = self.cursor;
-        if tmp >= self.max {
+    /// Get the value for the given position, clamping each coordinate to the
+    /// nearest edge of this array.
+    ///
+    /// # Panics
+    /// Panics if this array is empty.
+    pub fn get_clamped(&self, v : IVec2) -> &T {
+        assert!(!self.is_empty(), "Array is empty");
+
+        let max = IVec2::new(self.width as i32 - 1, self.height as i32 - 1);
+        self.local(self.to_local(v).clamp(IVec2::ZERO, max))
+    }
+
+    /// Get the value at the given offset from the center of this array, so
+    /// an offset of `(0, 0)` returns the middle cell. See `center`.
+    pub fn get_centered(&self, offset : IVec2) -> &T {
+        self.get(self.center() + offset)
+    }
+
+    /// Returns the array index for the given position or None if any
+    /// coordinate is negative or exceeds the dimensions of this array.
+    fn checked_index(&self, v : IVec2) -> Option<usize> {
+        if v.x < 0 || v.y < 0 || v.x as usize >= self.width || v.y as usize >= self.height {
             return None;
         }
 
-        self.cursor += 1;
-        let v = get_2d_from_1d_ivec2(self.width, tmp);
-
-        Some((v, &self.items[tmp]))
+        Some(get_1d_from_2d_ivec2(self.stride, v))
     }
-}
 
-impl<'a, T: std::default::Default> IntoIterator for &'a Array2d<T> {
-    type Item = (IVec2, &'a T);
+    /// Returns the array index for a position relative to the origin of
+    /// this array, see `new_with_origin`.
+    fn origin_index(&self, v : IVec2) -> Option<usize> {
+        self.checked_index(self.to_local(v))
+    }
 
-    type IntoIter = Array2dIter<'a, T>;
+    /// Translates a position relative to the origin into a local position
+    /// starting at zero.
+    fn to_local(&self, v : IVec2) -> IVec2 {
+        v.wrapping_sub(self.origin)
+    }
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter()
+    /// Moves this array to the given origin without touching its cells. See
+    /// `new_with_origin`.
+    pub fn with_origin(mut self, origin : IVec2) -> Self {
+        self.origin = origin;
+        self
     }
-}
 
-pub struct Array2dMutIter<'a, T: std::default::Default> {
-    items: &'a mut Vec<T>,
-    cursor: usize,
-    max: usize,
-    width: usize,
-}
+    /// Creates the error for a position that lies outside of this array.
+    fn out_of_bounds(&self, pos : IVec2) -> ArrayError<IVec2> {
+        ArrayError::OutOfBounds { pos, dims: self.dimensions() }
+    }
 
-impl<'a, T: std::default::Default> Iterator for Array2dMutIter<'a, T> {
-    type Item = (IVec2, &'a mut T);
+    /// Get the value for a local position, ignoring the origin.
+    fn local(&self, v : IVec2) -> &T {
+        &self.array[self.checked_index(v).expect("Invalid index")]
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let tmp = self.cursor;
-        self.cursor += 1;
-        if tmp >= self.max {
-            return None;
-        }
+    /// Get a mutable reference for a local position, ignoring the origin.
+    fn local_mut(&mut self, v : IVec2) -> &mut T {
+        let i = self.checked_index(v).expect("Invalid index");
+        &mut self.array[i]
+    }
 
-        let v = get_2d_from_1d_ivec2(self.width, self.cursor);
+    /// Get the value for a local position or None if it lies outside of
+    /// this array, ignoring the origin.
+    fn local_checked(&self, v : IVec2) -> Option<&T> {
+        self.checked_index(v).map(|i| &self.array[i])
+    }
 
-        let pt = self.items.as_mut_ptr();
-        unsafe { Some((v, &mut *pt)) }
+    /// Get a mutable reference for a local position or None if it lies
+    /// outside of this array, ignoring the origin.
+    fn local_mut_checked(&mut self, v : IVec2) -> Option<&mut T> {
+        self.checked_index(v).map(|i| &mut self.array[i])
     }
-}
 
-impl<'a, T: std::default::Default> IntoIterator for &'a mut Array2d<T> {
-    type Item = (IVec2, &'a mut T);
+    /// Maps a logical flat index onto the padded backing storage.
+    fn physical_index(&self, i : usize) -> usize {
+        if self.stride == self.width {
+            return i;
+        }
 
-    type IntoIter = Array2dMutIter<'a, T>;
+        let (x, y) = get_2d_from_1d(self.width, i);
+        get_1d_from_2d(self.stride, x, y)
+    }
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter_mut()
+    /// Creates an iterator over the logical cells in flat order, skipping
+    /// the row padding.
+    fn cells(&self) -> impl Iterator<Item = &T> {
+        let width = self.width;
+        self.padded_rows().flat_map(move |row| &row[..width])
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Splits the backing storage into rows including their padding. An
+    /// array without columns has no storage, so it yields no rows.
+    fn padded_rows(&self) -> std::slice::Chunks<'_, T> {
+        self.array.chunks(self.stride.max(1))
+    }
 
-    fn get_data_2d() -> Vec<(usize, usize, usize)> {
-        vec![
-            (4, 0, 0),
-            (4, 1, 0),
-            (4, 1, 1),
-            (4, 2, 1),
-            (4, 3, 1),
-            (4, 1, 2),
-            (4, 1, 3),
-            (4, 3, 3),
-            (8, 6, 7),
-            (8, 0, 7),
-            (8, 7, 7),
-        ]
+    /// Splits the backing storage mutably into rows including their padding.
+    fn padded_rows_mut(&mut self) -> std::slice::ChunksMut<'_, T> {
+        self.array.chunks_mut(self.stride.max(1))
     }
 
-    fn get_quantize_data() -> Vec<(f32, f32, f32, f32, f32)> {
-        vec![ 
-            ( 12.6,   8.4, 64.0,   0.0,  0.0),
-            ( 67.2,  12.8, 64.0,  64.0,  0.0),
-            (135.2,  63.9, 64.0, 128.0,  0.0),
-            ( 17.2, 127.9, 64.0,   0.0, 64.0),
-        ]
+    /// Returns true if the given position lies inside of this array, i.e.
+    /// in `[origin, origin + dimensions)`.
+    pub fn contains_point(&self, v : IVec2) -> bool {
+        self.origin_index(v).is_some()
     }
 
-    fn get_mapping_data() -> Vec<(f32, f32, f32, usize, usize)> {
-        vec![
-            (  0.0,  0.0, 64.0, 0, 0),
-            ( 64.0,  0.0, 64.0, 1, 0),
-            (128.0,  0.0, 64.0, 2, 0),
-            (  0.0, 64.0, 64.0, 0, 1),
-        ]
+    /// Returns the flat index of the given position as used by `Index<usize>`
+    /// and `get_flat`. The position is not checked against the bounds of this
+    /// array.
+    pub fn index_of(&self, v : IVec2) -> usize {
+        get_1d_from_2d_ivec2(self.width, self.to_local(v))
     }
 
-    #[test]
-    fn test_from_and_to_1d() {
-        let data = get_data_2d();
+    /// Returns the position of the given flat index. Inverse of `index_of`.
+    pub fn position_of(&self, i : usize) -> IVec2 {
+        self.origin + get_2d_from_1d_ivec2(self.width, i)
+    }
 
-        for (width, x1, y1) in data {
-            let t = get_1d_from_2d(width, x1, y1);
-            let (x2, y2) = get_2d_from_1d(width, t);
+    /// Creates an iterator over the orthogonal neighbors of the given
+    /// position. Neighbors outside of this array are skipped.
+    pub fn neighbors4(&self, v : IVec2) -> impl Iterator<Item = (IVec2, &T)> {
+        self.neighbors(v, &NEIGHBORS_4)
+    }
 
-            assert_eq!(x1, x2);
-            assert_eq!(y1, y2);
-        }
+    /// Creates an iterator over the orthogonal and diagonal neighbors of the
+    /// given position. Neighbors outside of this array are skipped.
+    pub fn neighbors8(&self, v : IVec2) -> impl Iterator<Item = (IVec2, &T)> {
+        self.neighbors(v, &NEIGHBORS_8)
     }
 
-    #[test]
-    fn test_from_and_to_1d_ivec2() {
-        let data = get_data_2d();
+    /// Returns the 3x3 block centered at `v` in flat order, starting at the
+    /// top left. Cells outside of this array are replaced by `border`.
+    pub fn moore_stencil<'a>(&'a self, v : IVec2, border : &'a T) -> [&'a T; 9] {
+        std::array::from_fn(|i| {
+            let offset = IVec2::new(i as i32 % 3 - 1, i as i32 / 3 - 1);
+            self.get_checked(v + offset).unwrap_or(border)
+        })
+    }
 
-        for (width, x1, y1) in data {
-            let s1 = IVec2 {
-                x: x1 as i32,
-                y: y1 as i32,
-            };
+    /// Yields the cells at the given offsets from `v` that lie inside of
+    /// this array.
+    fn neighbors<'a>(&'a self, v : IVec2, offsets : &'static [IVec2]) -> impl Iterator<Item = (IVec2, &'a T)> {
+        offsets.iter().filter_map(move |offset| {
+            let n = v + *offset;
+            self.get_checked(n).map(|value| (n, value))
+        })
+    }
 
-            let t = get_1d_from_2d_ivec2(width, s1);
-            let s2 = get_2d_from_1d_ivec2(width, t);
+    /// Resize this array to the given dimensions. Allocates 
+    /// the needed memory right away. Any row padding is removed.
+    pub fn resize(&mut self, width : usize, heigth : usize)
+    where
+        T: std::default::Default,
+    {
+        self.height = heigth;
+        self.width = width;
+        self.stride = width;
+        self.array.resize_with(checked_len(&[width, heigth]), || T::default());
+    }
 
-            assert_eq!(s1, s2);
+    /// Resize this array to the given dimensions while keeping every cell at
+    /// its position. Cells outside of the new bounds are dropped and new cells
+    /// are set to their default value. Any row padding is removed.
+    pub fn resize_preserving(&mut self, width : usize, height : usize)
+    where
+        T: std::default::Default,
+    {
+        let mut array : Vec<T> = Vec::new();
+        array.resize_with(checked_len(&[width, height]), || T::default());
+
+        let old = std::mem::replace(&mut self.array, array);
+        for (i, value) in old.into_iter().enumerate() {
+            let (x, y) = get_2d_from_1d(self.stride, i);
+            if x < self.width && x < width && y < height {
+                self.array[get_1d_from_2d(width, x, y)] = value;
+            }
         }
-    }
 
-    #[test]
-    fn test_into_iter() {
-        let test: Array2d<u64> = Array2d::new(2, 2);
-        assert_eq!(test.len(), 4);
+        self.width = width;
+        self.height = height;
+        self.stride = width;
+    }
 
-        for (_pos, value) in &test {
-            // Does this compile?
-            assert_eq!(*value, 0);
+    /// Resize this array to the given dimensions while keeping every cell at
+    /// its position, like `resize_preserving`, but set new cells to `value`
+    /// instead of their default value. Any row padding is removed.
+    pub fn resize_with_value(&mut self, width : usize, height : usize, value : T)
+    where
+        T: Clone,
+    {
+        let array = vec![value; checked_len(&[width, height])];
+
+        let old = std::mem::replace(&mut self.array, array);
+        for (i, value) in old.into_iter().enumerate() {
+            let (x, y) = get_2d_from_1d(self.stride, i);
+            if x < self.width && x < width && y < height {
+                self.array[get_1d_from_2d(width, x, y)] = value;
+            }
         }
+
+        self.width = width;
+        self.height = height;
+        self.stride = width;
     }
 
-    #[test]
+    /// Release any memory the backing storage holds beyond the cells of this
+    /// array, e.g. after resizing it to smaller dimensions.
+    pub fn shrink_to_fit(&mut self) {
+        self.array.shrink_to_fit();
+    }
+
+    /// Append a row at the bottom of this array. The row has to hold exactly
+    /// `width` cells, unless this array has no rows yet, in which case the
+    /// row defines the width. Rows are stored one after another, so existing
+    /// cells are not moved. The row padding of a padded array is filled with
+    /// clones of the last cell of the row.
+    pub fn push_row(&mut self, row : &[T])
+    where
+        T: Clone,
+    {
+        if self.height == 0 {
+            self.width = row.len();
+            self.stride = row.len();
+        }
+
+        assert_eq!(row.len(), self.width, "Invalid row length");
+
+        self.array.extend_from_slice(row);
+        match row.last() {
+            Some(last) => self.array.resize((self.height + 1) * self.stride, last.clone()),
+            None => {
+                // Rows without cells consist of padding only, so drop it.
+                self.array.clear();
+                self.stride = 0;
+            }
+        }
+
+        self.height += 1;
+    }
+
+    /// Append a column at the right of this array. The column has to hold
+    /// exactly `height` cells, unless this array has no columns yet, in which
+    /// case the column defines the height. Every row has to be moved, so this
+    /// rebuilds the backing storage and removes any row padding.
+    pub fn push_column(&mut self, column : &[T])
+    where
+        T: Clone,
+    {
+        if self.width == 0 {
+            self.height = column.len();
+        }
+
+        assert_eq!(column.len(), self.height, "Invalid column length");
+
+        let mut old = std::mem::take(&mut self.array).into_iter();
+        let mut array = Vec::with_capacity((self.width + 1) * self.height);
+        for value in column {
+            array.extend(old.by_ref().take(self.width));
+            array.push(value.clone());
+
+            // Drop the padding of the old row.
+            for _ in self.width..self.stride {
+                old.next();
+            }
+        }
+
+        self.array = array;
+        self.width += 1;
+        self.stride = self.width;
+    }
+
+    /// Remove row `y` and move every row below it up by one. Panics if the
+    /// row lies outside of this array.
+    pub fn remove_row(&mut self, y : i32) {
+        let start = self.row_start(y);
+        self.array.drain(start..start + self.stride);
+        self.height -= 1;
+    }
+
+    /// Remove column `x` and move every column to the right of it left by
+    /// one. Panics if the column lies outside of this array.
+    pub fn remove_column(&mut self, x : i32) {
+        let (x, stride) = (self.column_index(x), self.stride);
+        let mut i = 0;
+        self.array.retain(|_| {
+            let keep = i % stride != x;
+            i += 1;
+            keep
+        });
+
+        self.width -= 1;
+        self.stride -= 1;
+    }
+
+    /// Reserve memory for at least `additional_cells` more cells, so later
+    /// calls to `resize` do not have to reallocate. This only affects the
+    /// capacity of the backing storage, the dimensions stay the same.
+    pub fn reserve(&mut self, additional_cells : usize) {
+        self.array.reserve(additional_cells);
+    }
+    
+    /// Returns the number of items inside this array holds.
+    pub fn len(&self) -> usize {
+        self.width * self.height
+    }
+
+    /// Returns the number of columns of this array.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows of this array.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the width and height of this array.
+    pub fn dimensions(&self) -> IVec2 {
+        IVec2::new(self.width as i32, self.height as i32)
+    }
+
+    /// Returns the position of the middle cell, i.e. `(width / 2, height / 2)`.
+    /// For an even dimension the cell right after the middle is chosen.
+    pub fn center(&self) -> IVec2 {
+        self.origin + self.dimensions() / 2
+    }
+
+    /// Returns the number of cells between the starts of two consecutive
+    /// rows in the backing storage. Equals the width unless the rows are
+    /// padded.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Returns the smallest position accepted by the positional accessors.
+    /// This is zero unless the array was created with `new_with_origin`.
+    pub fn origin(&self) -> IVec2 {
+        self.origin
+    }
+
+    /// Returns true if this array holds no cells because one of its
+    /// dimensions is zero.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the backing storage including any row padding. The cells are
+    /// laid out row by row with x running fastest, so the cell at `(x, y)`
+    /// lives at `y * stride + x` and the slice holds `stride * height` items.
+    pub fn as_slice(&self) -> &[T] {
+        &self.array
+    }
+
+    /// Returns the backing storage mutably. See `as_slice` for the layout.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.array
+    }
+
+    /// Consumes this array and returns the backing storage laid out row by
+    /// row with `y * width + x`. Any row padding is removed in place.
+    pub fn into_vec(self) -> Vec<T> {
+        let mut array = self.array;
+        if self.stride != self.width {
+            let mut i = 0;
+            array.retain(|_| {
+                let keep = i % self.stride < self.width;
+                i += 1;
+                keep
+            });
+        }
+
+        array
+    }
+
+    /// Consumes this array and turns it into a 3d array with a depth of one.
+    /// Both arrays lay out a plane row by row, so the buffer is reused as is.
+    /// The origin is dropped, since a 3d array always starts at zero.
+    pub fn to_array3d(self) -> Array3d<T> {
+        let (width, height) = (self.width, self.height);
+        Array3d::from_vec(width, height, 1, self.into_vec())
+            .expect("A 2d array always fills exactly one slice")
+    }
+
+    /// Creates a new immutable iterator.
+    pub fn iter(&self) -> Array2dIter<'_, T> {
+        Array2dIter {
+            items: &self.array,
+            cursor: 0,
+            max: self.len(),
+            width: self.width,
+            stride: self.stride,
+            origin: self.origin,
+        }
+    }
+
+    /// Creates a new mutable iterator. To simply update every cell prefer
+    /// `for_each_mut`, the iterator is meant for combining the cells with
+    /// other iterators.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_flat_arrays::prelude::*;
+    /// let mut array : Array2d<i32> = Array2d::new(2, 2);
+    /// for (pos, value) in array.iter_mut() {
+    ///     *value = pos.x;
+    /// }
+    /// assert_eq!(*array.get(IVec2 { x : 1, y : 0 }), 1);
+    /// ```
+    pub fn iter_mut(&mut self) -> Array2dMutIter<'_, T> {
+        let len = self.len();
+
+        Array2dMutIter {
+            items: self.array.iter_mut(),
+            cursor: 0,
+            max: len,
+            width: self.width,
+            stride: self.stride,
+            origin: self.origin,
+        }
+    }
+
+    /// Creates a new parallel mutable iterator. Every cell is visited exactly
+    /// once along with its position.
+    #[cfg(feature = "rayon")]
+    pub fn par_iter_mut(&mut self) -> impl rayon::iter::ParallelIterator<Item = (IVec2, &mut T)>
+    where
+        T: Send,
+    {
+        use rayon::prelude::*;
+
+        let (width, origin) = (self.width, self.origin);
+
+        self.array
+            .par_chunks_mut(self.stride.max(1))
+            .enumerate()
+            .flat_map_iter(move |(y, row)| {
+                row[..width]
+                    .iter_mut()
+                    .enumerate()
+                    .map(move |(x, value)| (origin + IVec2::new(x as i32, y as i32), value))
+            })
+    }
+
+    /// Creates a new immutable iterator that yields the flat index of every
+    /// cell along with its position and value.
+    pub fn iter_indexed(&self) -> impl Iterator<Item = (usize, IVec2, &T)> {
+        self.iter()
+            .enumerate()
+            .map(|(i, (pos, value))| (i, pos, value))
+    }
+
+    /// Creates a new immutable iterator that skips every cell holding the
+    /// default value.
+    pub fn iter_non_default(&self) -> impl Iterator<Item = (IVec2, &T)>
+    where
+        T: PartialEq + std::default::Default,
+    {
+        let default = T::default();
+        self.iter().filter(move |(_, value)| **value != default)
+    }
+
+    /// Creates an iterator over every valid position of this array in flat
+    /// order without borrowing the data.
+    pub fn positions(&self) -> impl Iterator<Item = IVec2> {
+        let (width, origin) = (self.width, self.origin);
+        (0..self.len()).map(move |i| origin + get_2d_from_1d_ivec2(width, i))
+    }
+
+    /// Creates an iterator over the cells of row `y` from left to right.
+    /// Panics if the row lies outside of this array.
+    pub fn row(&self, y : i32) -> impl Iterator<Item = &T> {
+        self.row_slice(y).iter()
+    }
+
+    /// Returns the cells of row `y` as a contiguous slice. This relies on
+    /// the cells of a row being stored next to each other with x running
+    /// fastest. Panics if the row lies outside of this array.
+    pub fn row_slice(&self, y : i32) -> &[T] {
+        let start = self.row_start(y);
+        &self.array[start..start + self.width]
+    }
+
+    /// Returns the cells of row `y` as a contiguous mutable slice. Panics if
+    /// the row lies outside of this array.
+    pub fn row_slice_mut(&mut self, y : i32) -> &mut [T] {
+        let start = self.row_start(y);
+        &mut self.array[start..start + self.width]
+    }
+
+    /// Returns the index of the first cell of row `y` in the backing storage.
+    fn row_start(&self, y : i32) -> usize {
+        let y = y.wrapping_sub(self.origin.y);
+        assert!(y >= 0 && (y as usize) < self.height, "Invalid row");
+        y as usize * self.stride
+    }
+
+    /// Returns the local index of column `x`. Panics if the column lies
+    /// outside of this array.
+    fn column_index(&self, x : i32) -> usize {
+        let x = x.wrapping_sub(self.origin.x);
+        assert!(x >= 0 && (x as usize) < self.width, "Invalid column");
+        x as usize
+    }
+
+    /// Creates an iterator over the cells of column `x` from top to bottom.
+    /// Panics if the column lies outside of this array.
+    pub fn column(&self, x : i32) -> impl Iterator<Item = &T> {
+        let x = self.column_index(x);
+        self.array.iter().skip(x).step_by(self.stride)
+    }
+
+    /// Creates an iterator that yields an iterator for every row from top
+    /// to bottom. An array without columns still yields `height` empty rows.
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.height).map(move |y| {
+            let start = y * self.stride;
+            self.array[start..start + self.width].iter()
+        })
+    }
+
+    /// Overwrite every cell with a clone of the given value.
+    pub fn fill(&mut self, value : T)
+    where
+        T: Clone,
+    {
+        self.array.fill(value);
+    }
+
+    /// Overwrite every cell of row `y` with a clone of the given value.
+    /// Panics if the row lies outside of this array.
+    pub fn fill_row(&mut self, y : i32, value : T)
+    where
+        T: Clone,
+    {
+        self.row_slice_mut(y).fill(value);
+    }
+
+    /// Overwrite every cell of column `x` with a clone of the given value.
+    /// Panics if the column lies outside of this array.
+    pub fn fill_column(&mut self, x : i32, value : T)
+    where
+        T: Clone,
+    {
+        let x = self.column_index(x);
+        for row in self.padded_rows_mut() {
+            row[x] = value.clone();
+        }
+    }
+
+    /// Replace every cell equal to `from` with a clone of `to`. Returns the
+    /// number of replaced cells.
+    pub fn replace_all(&mut self, from : &T, to : T) -> usize
+    where
+        T: Clone + PartialEq,
+    {
+        let mut count = 0;
+        for (_, value) in self.iter_mut() {
+            if *value == *from {
+                *value = to.clone();
+                count += 1;
+            }
+        }
+
+        count
+    }
+
+    /// Overwrite every cell with the values returned by calling `f`
+    /// repeatedly.
+    pub fn fill_with(&mut self, f : impl FnMut() -> T) {
+        self.array.fill_with(f);
+    }
+
+    /// Overwrite every cell with the items of `data`, which is laid out row
+    /// by row. Panics if `data` does not hold exactly `len` items.
+    pub fn copy_from(&mut self, data : &[T])
+    where
+        T: Copy,
+    {
+        assert_eq!(data.len(), self.len(), "Invalid data length");
+
+        if self.stride == self.width {
+            self.array.copy_from_slice(data);
+            return;
+        }
+
+        let width = self.width;
+        for (row, src) in self.padded_rows_mut().zip(data.chunks(width.max(1))) {
+            row[..width].copy_from_slice(src);
+        }
+    }
+
+    /// Reset every cell to its default value. The allocated memory is kept.
+    pub fn clear(&mut self)
+    where
+        T: std::default::Default,
+    {
+        self.array.fill_with(T::default);
+    }
+
+    /// Keep every cell for which `pred` returns true and reset all other
+    /// cells to their default value.
+    pub fn retain(&mut self, pred : impl Fn(IVec2, &T) -> bool)
+    where
+        T: std::default::Default,
+    {
+        for (pos, value) in self.iter_mut() {
+            if !pred(pos, value) {
+                *value = T::default();
+            }
+        }
+    }
+
+    /// Call `f` with the position and a mutable reference of every cell in
+    /// flat order.
+    pub fn apply(&mut self, mut f : impl FnMut(IVec2, &mut T)) {
+        let (width, origin) = (self.width, self.origin);
+
+        for (y, row) in self.padded_rows_mut().enumerate() {
+            for (x, value) in row[..width].iter_mut().enumerate() {
+                f(origin + IVec2::new(x as i32, y as i32), value);
+            }
+        }
+    }
+
+    /// Call `f` with the position and a mutable reference of every cell in
+    /// flat order. This is the preferred way to update every cell.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_flat_arrays::prelude::*;
+    /// let mut array : Array2d<i32> = Array2d::new(2, 2);
+    /// array.for_each_mut(|pos, value| *value = pos.x);
+    /// assert_eq!(*array.get(IVec2 { x : 1, y : 0 }), 1);
+    /// ```
+    pub fn for_each_mut(&mut self, f : impl FnMut(IVec2, &mut T)) {
+        self.apply(f);
+    }
+
+    /// Overwrite every cell with the value `f` returns for it. Unlike `map`
+    /// this reuses the memory of this array.
+    pub fn map_in_place(&mut self, mut f : impl FnMut(&T) -> T) {
+        self.apply(|_, value| *value = f(value));
+    }
+
+    /// Creates a new array of the same dimensions by applying `f` to every
+    /// cell of this array.
+    pub fn map<U>(&self, f : impl Fn(IVec2, &T) -> U) -> Array2d<U> {
+        Array2d {
+            width: self.width,
+            height: self.height,
+            stride: self.width,
+            origin: IVec2::ZERO,
+            array: self.iter().map(|(pos, value)| f(pos, value)).collect(),
+        }
+    }
+
+    /// Returns true if `other` has the same width and height as this array.
+    pub fn dimensions_match<U>(&self, other : &Array2d<U>) -> bool {
+        self.width == other.width && self.height == other.height
+    }
+
+    /// Creates a new array by combining the cells of this array and `other`
+    /// at the same positions using `f`. Returns an error if the dimensions
+    /// of both arrays differ.
+    pub fn zip<U, V>(&self, other : &Array2d<U>, f : impl Fn(&T, &U) -> V) -> Result<Array2d<V>, ArrayError<IVec2>> {
+        if !self.dimensions_match(other) {
+            return Err(ArrayError::DimMismatch { expected: self.dimensions(), got: other.dimensions() });
+        }
+
+        Ok(Array2d {
+            width: self.width,
+            height: self.height,
+            stride: self.width,
+            origin: IVec2::ZERO,
+            array: self.cells().zip(other.cells()).map(|(a, b)| f(a, b)).collect(),
+        })
+    }
+
+    /// Copies the rectangle `[min, min + size)` into a new array of the given
+    /// size. Returns an error if the region is empty, if `size` is negative or
+    /// if the region does not lie completely inside of this array.
+    pub fn sub_region(&self, min : IVec2, size : IVec2) -> Result<Array2d<T>, ArrayError<IVec2>>
+    where
+        T: Clone,
+    {
+        if size.x < 0 || size.y < 0 {
+            return Err(self.out_of_bounds(min + size));
+        }
+
+        if size.x == 0 || size.y == 0 {
+            return Err(ArrayError::ZeroDimension);
+        }
+
+        for corner in [min, min + size - IVec2::ONE] {
+            if !self.contains_point(corner) {
+                return Err(self.out_of_bounds(corner));
+            }
+        }
+
+        Ok(Array2d::from_fn(size.x as usize, size.y as usize, |pos| self.get(min + pos).clone()))
+    }
+
+    /// Creates an iterator that splits this array into blocks of `chunk_size`
+    /// in flat order and yields the origin of every block along with a copy of
+    /// its cells. Blocks at the right and bottom edge shrink to fit.
+    pub fn chunks(&self, chunk_size : IVec2) -> impl Iterator<Item = (IVec2, Array2d<T>)> + '_
+    where
+        T: Clone,
+    {
+        assert!(chunk_size.x > 0 && chunk_size.y > 0, "Invalid chunk size");
+
+        let dims = self.dimensions();
+        (0..dims.y).step_by(chunk_size.y as usize).flat_map(move |y| {
+            (0..dims.x).step_by(chunk_size.x as usize).map(move |x| {
+                let corner = IVec2::new(x, y);
+                let size = chunk_size.min(dims - corner);
+                let min = self.origin + corner;
+                let chunk = self.sub_region(min, size).expect("Chunks always lie inside of the array");
+                (min, chunk)
+            })
+        })
+    }
+
+    /// Creates an iterator over every window of `size` that lies completely
+    /// inside of this array. Yields the top left position of every window
+    /// along with its cells in flat order. Unlike `chunks` the windows
+    /// overlap each other.
+    pub fn windows(&self, size : IVec2) -> impl Iterator<Item = (IVec2, Vec<&T>)> {
+        assert!(size.x > 0 && size.y > 0, "Invalid window size");
+
+        let last = self.dimensions() - size;
+        (0..=last.y).flat_map(move |y| {
+            (0..=last.x).map(move |x| {
+                let corner = IVec2::new(x, y);
+                let cells = (0..size.y)
+                    .flat_map(|dy| (0..size.x).map(move |dx| IVec2::new(dx, dy)))
+                    .map(|offset| self.local(corner + offset))
+                    .collect();
+
+                (self.origin + corner, cells)
+            })
+        })
+    }
+
+    /// Copies `src` into this array with its origin placed at `dest`. Cells
+    /// that would land outside of this array are clipped.
+    pub fn blit(&mut self, dest : IVec2, src : &Array2d<T>)
+    where
+        T: Clone,
+    {
+        for (pos, value) in src {
+            if let Some(cell) = self.get_mut_checked(dest + pos - src.origin) {
+                *cell = value.clone();
+            }
+        }
+    }
+
+    /// Overwrite the rectangle `[min, min + size)` with `data`, which is laid
+    /// out row by row. Returns an error and leaves this array untouched if the
+    /// rectangle does not lie completely inside of this array, if `size` is
+    /// negative or if `data` does not hold exactly `size.x * size.y` items.
+    pub fn set_region(&mut self, min : IVec2, size : IVec2, data : &[T]) -> Result<(), ArrayError<IVec2>>
+    where
+        T: Clone,
+    {
+        if size.x < 0 || size.y < 0 {
+            return Err(self.out_of_bounds(min + size));
+        }
+
+        let expected = size.x as usize * size.y as usize;
+        if data.len() != expected {
+            return Err(ArrayError::SizeMismatch { expected, got: data.len() });
+        }
+
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        for corner in [min, min + size - IVec2::ONE] {
+            if !self.contains_point(corner) {
+                return Err(self.out_of_bounds(corner));
+            }
+        }
+
+        for (y, row) in data.chunks(size.x as usize).enumerate() {
+            let start = self.origin_index(min + IVec2::new(0, y as i32)).expect("Invalid index");
+            self.array[start..start + row.len()].clone_from_slice(row);
+        }
+
+        Ok(())
+    }
+
+    /// Creates a new array with swapped dimensions where the cell at `(x, y)`
+    /// holds the value of this array at `(y, x)`.
+    pub fn transpose(&self) -> Array2d<T>
+    where
+        T: Clone,
+    {
+        Array2d::from_fn(self.height, self.width, |pos| self.local(pos.yx()).clone())
+    }
+
+    /// Creates a new array rotated by 90 degrees clockwise, with rows running
+    /// from top to bottom. The width and height of the result are swapped.
+    pub fn rotate_cw(&self) -> Array2d<T>
+    where
+        T: Clone,
+    {
+        let max_y = self.height as i32 - 1;
+        Array2d::from_fn(self.height, self.width, |pos| {
+            self.local(IVec2::new(pos.y, max_y - pos.x)).clone()
+        })
+    }
+
+    /// Creates a new array rotated by 90 degrees counterclockwise, with rows
+    /// running from top to bottom. The width and height of the result are
+    /// swapped.
+    pub fn rotate_ccw(&self) -> Array2d<T>
+    where
+        T: Clone,
+    {
+        let max_x = self.width as i32 - 1;
+        Array2d::from_fn(self.height, self.width, |pos| {
+            self.local(IVec2::new(max_x - pos.y, pos.x)).clone()
+        })
+    }
+
+    /// Mirror the columns of this array in place, so the leftmost column
+    /// becomes the rightmost one. The center column of an odd width stays put.
+    pub fn flip_x(&mut self) {
+        let width = self.width;
+        for row in self.padded_rows_mut() {
+            row[..width].reverse();
+        }
+    }
+
+    /// Mirror the rows of this array in place, so the top row becomes the
+    /// bottom one. The center row of an odd height stays put.
+    pub fn flip_y(&mut self) {
+        for y in 0..self.height / 2 {
+            let mirrored = self.height - 1 - y;
+
+            for x in 0..self.width {
+                self.array.swap(
+                    get_1d_from_2d(self.stride, x, y),
+                    get_1d_from_2d(self.stride, x, mirrored),
+                );
+            }
+        }
+    }
+
+    /// Returns the number of cells for which `pred` returns true.
+    pub fn count_where(&self, pred : impl Fn(&T) -> bool) -> usize {
+        self.cells().filter(|value| pred(value)).count()
+    }
+
+    /// Returns the position of the first cell in flat order for which `pred`
+    /// returns true.
+    pub fn find_position(&self, pred : impl Fn(&T) -> bool) -> Option<IVec2> {
+        self.cells()
+            .position(pred)
+            .map(|i| self.position_of(i))
+    }
+
+    /// Replace the region of cells that are orthogonally connected to `start`
+    /// and hold the same value as `start` with `new_value`. Does nothing if
+    /// `new_value` already equals the value at `start`.
+    pub fn flood_fill(&mut self, start : IVec2, new_value : T)
+    where
+        T: Clone + PartialEq,
+    {
+        let start = self.to_local(start);
+        let old_value = self.local(start).clone();
+        if old_value == new_value {
+            return;
+        }
+
+        // Cells are replaced when pushed, so every cell enters the stack once.
+        *self.local_mut(start) = new_value.clone();
+        let mut stack = vec![start];
+
+        while let Some(pos) = stack.pop() {
+            for offset in NEIGHBORS_4 {
+                let n = pos + offset;
+                if let Some(cell) = self.local_mut_checked(n) {
+                    if *cell == old_value {
+                        *cell = new_value.clone();
+                        stack.push(n);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Write `value` to every cell on the line from `a` to `b`, both ends
+    /// included, using Bresenham's algorithm. Cells outside of this array
+    /// are skipped.
+    pub fn draw_line(&mut self, a : IVec2, b : IVec2, value : T)
+    where
+        T: Clone,
+    {
+        let (a, b) = (self.to_local(a), self.to_local(b));
+        let dx = (b.x - a.x).abs();
+        let dy = -(b.y - a.y).abs();
+        let step = IVec2::new((b.x - a.x).signum(), (b.y - a.y).signum());
+        let mut error = dx + dy;
+        let mut pos = a;
+
+        loop {
+            if let Some(cell) = self.local_mut_checked(pos) {
+                *cell = value.clone();
+            }
+
+            if pos == b {
+                break;
+            }
+
+            let e2 = 2 * error;
+            if e2 >= dy {
+                error += dy;
+                pos.x += step.x;
+            }
+
+            if e2 <= dx {
+                error += dx;
+                pos.y += step.y;
+            }
+        }
+    }
+
+    /// Write `value` to every cell of the rectangle `[min, min + size)`.
+    /// Cells outside of this array are skipped.
+    pub fn fill_rect(&mut self, min : IVec2, size : IVec2, value : T)
+    where
+        T: Clone,
+    {
+        let min = self.to_local(min);
+        let lo = min.max(IVec2::ZERO);
+        let hi = (min + size).min(self.dimensions());
+
+        for y in lo.y..hi.y {
+            for x in lo.x..hi.x {
+                *self.local_mut(IVec2::new(x, y)) = value.clone();
+            }
+        }
+    }
+
+    /// Write `value` to the border cells of the rectangle `[min, min + size)`
+    /// and leave its interior untouched. Cells outside of this array are
+    /// skipped.
+    pub fn draw_rect_outline(&mut self, min : IVec2, size : IVec2, value : T)
+    where
+        T: Clone,
+    {
+        if size.x <= 0 || size.y <= 0 {
+            return;
+        }
+
+        let max = min + size - IVec2::ONE;
+        self.draw_line(min, IVec2::new(max.x, min.y), value.clone());
+        self.draw_line(IVec2::new(min.x, max.y), max, value.clone());
+        self.draw_line(min, IVec2::new(min.x, max.y), value.clone());
+        self.draw_line(IVec2::new(max.x, min.y), max, value);
+    }
+
+    /// Write `value` to every cell whose euclidean distance to `center` is at
+    /// most `radius`, i.e. `dx * dx + dy * dy <= radius * radius`. A radius of
+    /// one therefore covers the center and its orthogonal neighbors. Cells
+    /// outside of this array are skipped.
+    pub fn fill_circle(&mut self, center : IVec2, radius : i32, value : T)
+    where
+        T: Clone,
+    {
+        let center = self.to_local(center);
+        for y in -radius..=radius {
+            for x in -radius..=radius {
+                if x * x + y * y > radius * radius {
+                    continue;
+                }
+
+                if let Some(cell) = self.local_mut_checked(center + IVec2::new(x, y)) {
+                    *cell = value.clone();
+                }
+            }
+        }
+    }
+
+    /// Creates a new array where every cell is the weighted sum of its 3x3
+    /// neighborhood in this array. `kernel[row][column]` holds the weight of
+    /// the cell at offset `(column - 1, row - 1)`. Cells outside of this array
+    /// are replaced by the nearest edge cell.
+    pub fn convolve3x3(&self, kernel : [[f32; 3]; 3]) -> Array2d<T>
+    where
+        T: Copy + Into<f32> + From<f32>,
+    {
+        Array2d::from_fn(self.width, self.height, |pos| {
+            let mut sum = 0.0;
+            for (dy, weights) in kernel.iter().enumerate() {
+                for (dx, weight) in weights.iter().enumerate() {
+                    let offset = IVec2::new(dx as i32 - 1, dy as i32 - 1);
+                    sum += weight * (*self.get_clamped(self.origin + pos + offset)).into();
+                }
+            }
+
+            T::from(sum)
+        })
+    }
+
+    /// Sample this array at a fractional position by interpolating between the
+    /// four surrounding cells. Positions are given in cells, so the center of
+    /// the cell `(x, y)` lies at `(x + 0.5, y + 0.5)`. Positions beyond the
+    /// outer cell centers are clamped to the edge.
+    ///
+    /// # Panics
+    /// Panics if this array is empty.
+    pub fn sample_bilinear(&self, p : Vec2) -> f32
+    where
+        T: Copy + Into<f32>,
+    {
+        assert!(!self.is_empty(), "Array is empty");
+
+        let max = self.dimensions() - IVec2::ONE;
+        let q = (p - self.origin.as_vec2() - Vec2::splat(0.5)).clamp(Vec2::ZERO, max.as_vec2());
+
+        let lo = q.floor().as_ivec2();
+        let hi = (lo + IVec2::ONE).min(max);
+        let t = q - lo.as_vec2();
+
+        let value = |x : i32, y : i32| -> f32 { (*self.local(IVec2::new(x, y))).into() };
+        let top = value(lo.x, lo.y) * (1.0 - t.x) + value(hi.x, lo.y) * t.x;
+        let bottom = value(lo.x, hi.y) * (1.0 - t.x) + value(hi.x, hi.y) * t.x;
+
+        top * (1.0 - t.y) + bottom * t.y
+    }
+
+    /// Creates a new array holding the approximate euclidean distance from
+    /// every cell to the nearest cell for which `is_obstacle` returns true.
+    /// Obstacles get a distance of zero. The distances are computed with a two
+    /// pass chamfer transform using a weight of one for orthogonal and the
+    /// square root of two for diagonal steps. Without any obstacle every cell
+    /// holds `f32::INFINITY`.
+    pub fn distance_field(&self, is_obstacle : impl Fn(&T) -> bool) -> Array2d<f32> {
+        const FORWARD : [(IVec2, f32); 4] = [
+            (IVec2::new(-1, 0), 1.0),
+            (IVec2::new(-1, -1), std::f32::consts::SQRT_2),
+            (IVec2::new(0, -1), 1.0),
+            (IVec2::new(1, -1), std::f32::consts::SQRT_2),
+        ];
+
+        fn relax(field : &mut Array2d<f32>, pos : IVec2, sign : i32) {
+            let mut best = *field.local(pos);
+            for (offset, weight) in FORWARD {
+                if let Some(n) = field.local_checked(pos + offset * sign) {
+                    best = best.min(n + weight);
+                }
+            }
+
+            *field.local_mut(pos) = best;
+        }
+
+        let mut field = self.map(|_, value| if is_obstacle(value) { 0.0 } else { f32::INFINITY });
+        let (width, height) = (self.width as i32, self.height as i32);
+
+        // The forward pass propagates from the top left, the backward pass
+        // mirrors the offsets and propagates from the bottom right.
+        for y in 0..height {
+            for x in 0..width {
+                relax(&mut field, IVec2::new(x, y), 1);
+            }
+        }
+
+        for y in (0..height).rev() {
+            for x in (0..width).rev() {
+                relax(&mut field, IVec2::new(x, y), -1);
+            }
+        }
+
+        field
+    }
+
+    /// Finds a shortest path from `start` to `goal` over orthogonally
+    /// connected cells for which `passable` returns true, using A* with a
+    /// manhattan heuristic. The path includes both ends. Returns None if the
+    /// goal can not be reached or either end is not passable.
+    pub fn find_path(&self, start : IVec2, goal : IVec2, passable : impl Fn(&T) -> bool) -> Option<Vec<IVec2>> {
+        use std::cmp::Reverse;
+        use std::collections::BinaryHeap;
+
+        let (start, goal) = (self.to_local(start), self.to_local(goal));
+        let is_open = |pos : IVec2| self.local_checked(pos).is_some_and(&passable);
+        if !is_open(start) || !is_open(goal) {
+            return None;
+        }
+
+        let width = self.width;
+        let heuristic = |pos : IVec2| (goal - pos).abs().element_sum();
+
+        let mut costs = vec![i32::MAX; self.len()];
+        let mut came_from : Vec<Option<usize>> = vec![None; self.len()];
+        let mut open = BinaryHeap::new();
+
+        costs[get_1d_from_2d_ivec2(width, start)] = 0;
+        open.push(Reverse((heuristic(start), 0, get_1d_from_2d_ivec2(width, start))));
+
+        while let Some(Reverse((_, cost, i))) = open.pop() {
+            // Skip entries that were superseded by a cheaper route.
+            if cost > costs[i] {
+                continue;
+            }
+
+            let pos = get_2d_from_1d_ivec2(width, i);
+            if pos == goal {
+                let mut path = vec![self.position_of(i)];
+                let mut current = i;
+                while let Some(previous) = came_from[current] {
+                    path.push(self.position_of(previous));
+                    current = previous;
+                }
+
+                path.reverse();
+                return Some(path);
+            }
+
+            for offset in NEIGHBORS_4 {
+                let n = pos + offset;
+                if !is_open(n) {
+                    continue;
+                }
+
+                let j = get_1d_from_2d_ivec2(width, n);
+                if cost + 1 < costs[j] {
+                    costs[j] = cost + 1;
+                    came_from[j] = Some(i);
+                    open.push(Reverse((cost + 1 + heuristic(n), cost + 1, j)));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Creates the next generation of a cellular automaton. `rule` is called
+    /// for every cell with its current value and the values of its neighbors
+    /// inside of this array and returns the value of the cell in the new array.
+    pub fn step_cellular(&self, rule : impl Fn(&T, &[&T]) -> T) -> Array2d<T> {
+        let mut neighbors : Vec<&T> = Vec::with_capacity(NEIGHBORS_8.len());
+
+        Array2d::from_fn(self.width, self.height, |pos| {
+            neighbors.clear();
+            neighbors.extend(self.neighbors8(self.origin + pos).map(|(_, value)| value));
+            rule(self.local(pos), &neighbors)
+        })
+    }
+
+    /// Returns the smallest value of this array or None if it is empty.
+    pub fn min_value(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.cells().min()
+    }
+
+    /// Returns the largest value of this array or None if it is empty.
+    pub fn max_value(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.cells().max()
+    }
+
+    /// Returns the sum of all cells of this array.
+    pub fn sum(&self) -> T
+    where
+        T: Copy + std::iter::Sum<T>,
+    {
+        self.cells().copied().sum()
+    }
+
+    /// Returns the position of the first cell in flat order that holds the
+    /// largest value or None if this array is empty.
+    pub fn argmax(&self) -> Option<IVec2>
+    where
+        T: Ord,
+    {
+        self.iter()
+            .reduce(|best, item| if item.1 > best.1 { item } else { best })
+            .map(|(pos, _)| pos)
+    }
+
+    /// Returns the position of the first cell in flat order that holds the
+    /// smallest value or None if this array is empty.
+    pub fn argmin(&self) -> Option<IVec2>
+    where
+        T: Ord,
+    {
+        self.iter()
+            .reduce(|best, item| if item.1 < best.1 { item } else { best })
+            .map(|(pos, _)| pos)
+    }
+}
+
+impl<T> Index<usize> for Array2d<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        assert!(index < self.len());
+        &self.array[self.physical_index(index)]
+    }
+}
+
+impl<T> IndexMut<usize> for Array2d<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        assert!(index < self.len());
+        let i = self.physical_index(index);
+        &mut self.array[i]
+    }
+}
+
+impl<T> Index<IVec2> for Array2d<T> {
+    type Output = T;
+
+    fn index(&self, index: IVec2) -> &Self::Output {
+        self.get(index)
+    }
+}
+
+impl<T> IndexMut<IVec2> for Array2d<T> {
+    fn index_mut(&mut self, index: IVec2) -> &mut T {
+        self.get_mut(index)
+    }
+}
+
+impl<T: Clone> Clone for Array2d<T> {
+    fn clone(&self) -> Self {
+        Array2d {
+            width: self.width,
+            height: self.height,
+            stride: self.stride,
+            origin: self.origin,
+            array: self.array.clone(),
+        }
+    }
+}
+
+/// Creates a 1x1 array holding a single default value.
+impl<T: std::default::Default> Default for Array2d<T> {
+    fn default() -> Self {
+        Array2d::new(1, 1)
+    }
+}
+
+impl<T: PartialEq> PartialEq for Array2d<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && self.origin == other.origin
+            && self.cells().eq(other.cells())
+    }
+}
+
+impl<T: Eq> Eq for Array2d<T> {}
+
+/// Orders arrays by width, then by height, then by their cells in flat order
+/// and finally by their origin. Arrays of different dimensions are therefore
+/// ordered by their size alone, regardless of their content.
+impl<T: PartialOrd> PartialOrd for Array2d<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        match (self.width, self.height).cmp(&(other.width, other.height)) {
+            std::cmp::Ordering::Equal => match self.cells().partial_cmp(other.cells()) {
+                Some(std::cmp::Ordering::Equal) => Some(self.origin.to_array().cmp(&other.origin.to_array())),
+                ordering => ordering,
+            },
+            ordering => Some(ordering),
+        }
+    }
+}
+
+/// See `PartialOrd`.
+impl<T: Ord> Ord for Array2d<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.width, self.height)
+            .cmp(&(other.width, other.height))
+            .then_with(|| self.cells().cmp(other.cells()))
+            .then_with(|| self.origin.to_array().cmp(&other.origin.to_array()))
+    }
+}
+
+/// Hashes the dimensions and the origin followed by the cells in flat order.
+/// Row padding is skipped, matching `PartialEq`.
+impl<T: std::hash::Hash> std::hash::Hash for Array2d<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.width.hash(state);
+        self.height.hash(state);
+        self.origin.hash(state);
+
+        for value in self.cells() {
+            value.hash(state);
+        }
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Array2d<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Array2d {{ width: {}, height: {} }}", self.width, self.height)?;
+
+        for row in self.padded_rows() {
+            writeln!(f, "{:?}", &row[..self.width])?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for Array2d<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("Array2d", 4)?;
+        state.serialize_field("width", &self.width)?;
+        state.serialize_field("height", &self.height)?;
+        state.serialize_field("origin", &self.origin.to_array())?;
+        state.serialize_field("data", &self.cells().collect::<Vec<_>>())?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Array2d<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Array2d")]
+        struct Raw<T> {
+            width: usize,
+            height: usize,
+            #[serde(default)]
+            origin: [i32; 2],
+            data: Vec<T>,
+        }
+
+        let raw = Raw::<T>::deserialize(deserializer)?;
+
+        let mut array = Array2d::from_vec(raw.width, raw.height, raw.data).map_err(D::Error::custom)?;
+        array.origin = IVec2::from_array(raw.origin);
+        Ok(array)
+    }
+}
+
+pub struct Array2dIter<'a, T> {
+    items: &'a Vec<T>,
+    cursor: usize,
+    max: usize,
+    width: usize,
+    stride: usize,
+    origin: IVec2,
+}
+
+impl<'a, T> Iterator for Array2dIter<'a, T> {
+    type Item = (IVec2, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let tmp = self.cursor;
+        if tmp >= self.max {
+            return None;
+        }
+
+        self.cursor += 1;
+        let v = get_2d_from_1d_ivec2(self.width, tmp);
+
+        Some((self.origin + v, &self.items[get_1d_from_2d_ivec2(self.stride, v)]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.max - self.cursor;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Array2dIter<'_, T> {}
+
+impl<T> DoubleEndedIterator for Array2dIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.cursor >= self.max {
+            return None;
+        }
+
+        // max is the exclusive back cursor.
+        self.max -= 1;
+        let v = get_2d_from_1d_ivec2(self.width, self.max);
+
+        Some((self.origin + v, &self.items[get_1d_from_2d_ivec2(self.stride, v)]))
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Array2d<T> {
+    type Item = (IVec2, &'a T);
+
+    type IntoIter = Array2dIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub struct Array2dMutIter<'a, T> {
+    items: std::slice::IterMut<'a, T>,
+    cursor: usize,
+    max: usize,
+    width: usize,
+    stride: usize,
+    origin: IVec2,
+}
+
+impl<'a, T> Iterator for Array2dMutIter<'a, T> {
+    type Item = (IVec2, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let tmp = self.cursor;
+        if tmp >= self.max {
+            return None;
+        }
+
+        self.cursor += 1;
+        let v = get_2d_from_1d_ivec2(self.width, tmp);
+
+        // Skip the padding at the end of the previous row.
+        let skip = if v.x == 0 && v.y > 0 { self.stride - self.width } else { 0 };
+        self.items.nth(skip).map(|value| (self.origin + v, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.max - self.cursor;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Array2dMutIter<'_, T> {}
+
+impl<'a, T> IntoIterator for &'a mut Array2d<T> {
+    type Item = (IVec2, &'a mut T);
+
+    type IntoIter = Array2dMutIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+pub struct Array2dIntoIter<T> {
+    items: std::iter::Enumerate<std::vec::IntoIter<T>>,
+    width: usize,
+    origin: IVec2,
+}
+
+impl<T> Iterator for Array2dIntoIter<T> {
+    type Item = (IVec2, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.items.next().map(|(i, value)| (self.origin + get_2d_from_1d_ivec2(self.width, i), value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.items.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for Array2dIntoIter<T> {}
+
+/// Consumes the array and yields every cell along with its position in flat
+/// order.
+impl<T> IntoIterator for Array2d<T> {
+    type Item = (IVec2, T);
+
+    type IntoIter = Array2dIntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let (width, origin) = (self.width, self.origin);
+
+        Array2dIntoIter {
+            items: self.into_vec().into_iter().enumerate(),
+            width,
+            origin,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_data_2d() -> Vec<(usize, usize, usize)> {
+        vec![
+            (4, 0, 0),
+            (4, 1, 0),
+            (4, 1, 1),
+            (4, 2, 1),
+            (4, 3, 1),
+            (4, 1, 2),
+            (4, 1, 3),
+            (4, 3, 3),
+            (8, 6, 7),
+            (8, 0, 7),
+            (8, 7, 7),
+        ]
+    }
+
+    fn get_quantize_data() -> Vec<(f32, f32, f32, f32, f32)> {
+        vec![ 
+            ( 12.6,   8.4, 64.0,   0.0,  0.0),
+            ( 67.2,  12.8, 64.0,  64.0,  0.0),
+            (135.2,  63.9, 64.0, 128.0,  0.0),
+            ( 17.2, 127.9, 64.0,   0.0, 64.0),
+        ]
+    }
+
+    fn get_mapping_data() -> Vec<(f32, f32, f32, usize, usize)> {
+        vec![
+            (  0.0,  0.0, 64.0, 0, 0),
+            ( 64.0,  0.0, 64.0, 1, 0),
+            (128.0,  0.0, 64.0, 2, 0),
+            (  0.0, 64.0, 64.0, 0, 1),
+        ]
+    }
+
+    #[test]
+    fn test_from_and_to_1d() {
+        let data = get_data_2d();
+
+        for (width, x1, y1) in data {
+            let t = get_1d_from_2d(width, x1, y1);
+            let (x2, y2) = get_2d_from_1d(width, t);
+
+            assert_eq!(x1, x2);
+            assert_eq!(y1, y2);
+        }
+    }
+
+    #[test]
+    fn test_from_and_to_1d_ivec2() {
+        let data = get_data_2d();
+
+        for (width, x1, y1) in data {
+            let s1 = IVec2 {
+                x: x1 as i32,
+                y: y1 as i32,
+            };
+
+            let t = get_1d_from_2d_ivec2(width, s1);
+            let s2 = get_2d_from_1d_ivec2(width, t);
+
+            assert_eq!(s1, s2);
+        }
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let test: Array2d<u64> = Array2d::new(2, 2);
+        assert_eq!(test.len(), 4);
+
+        for (_pos, value) in &test {
+            // Does this compile?
+            assert_eq!(*value, 0);
+        }
+    }
+
+    #[test]
     fn test_into_iter_mut() {
         let test: Array2d<i64> = Array2d::new(2, 2);
         assert_eq!(test.len(), 4);
@@ -378,63 +2132,1873 @@ mod tests {
     }
 
     #[test]
-    fn test_getter_setter() {
-        let mut test: Array2d<usize> = Array2d::new(2, 2);
-        assert_eq!(test.len(), 4);
+    fn test_getter_setter() {
+        let mut test: Array2d<usize> = Array2d::new(2, 2);
+        assert_eq!(test.len(), 4);
+
+        for i in 0..test.len() {
+            test[i] = i;
+            let comp = test[i];
+
+            assert_eq!(i, comp);
+        }
+    }
+
+    #[test]
+    fn test_resize_array() {
+        let mut test : Array2d<usize> = Array2d::new(2, 2);
+        assert_eq!(test.len(), 4);
+        test.resize(3, 3);
+        assert_eq!(test.len(), 9);
+    }
+
+    #[test]
+    fn test_getter_and_setter() {
+        let mut test : Array2d<usize> = Array2d::new(4, 4);
+        assert_eq!(test.len(), 16);
+
+        let mut pos = IVec2{ x : 0, y : 0};
+        assert_eq!(*test.get(pos), 0);
+        test.set(pos, 1);
+        assert_eq!(*test.get(pos), 1);
+
+        pos = IVec2{ x : 3, y : 3};
+        assert_eq!(*test.get(pos), 0);
+        test.set(pos, 64);
+        assert_eq!(*test.get(pos), 64);
+    }
+
+    #[test]
+    fn test_quantize_element() {
+        let data = get_quantize_data();
+        for (x, y, size, x1, y1) in data {
+            let vec = Vec2{ x, y };
+            let r = quantize_to_grid(vec, size);
+
+            assert_eq!(r.x, x1);
+            assert_eq!(r.y, y1);
+        }
+    }
+
+    #[test]
+    fn test_map_element() {
+        let data = get_mapping_data();
+        for (x, y, size, x1, y1) in data {
+            let vec = Vec2{ x, y };
+            let r = map_to_grid_vec2(vec, size);
+
+            assert_eq!(r.x, x1 as i32);
+            assert_eq!(r.y, y1 as i32);
+        }
+    }
+
+    #[test]
+    fn test_iter_mut_writes_distinct_cells() {
+        let mut test : Array2d<i32> = Array2d::new(3, 3);
+
+        for (pos, value) in &mut test {
+            *value = pos.x + pos.y;
+        }
+
+        for x in 0..3 {
+            for y in 0..3 {
+                let pos = IVec2 { x, y };
+                assert_eq!(*test.get(pos), x + y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_iter_mut_method() {
+        let mut test : Array2d<i32> = Array2d::new(2, 2);
+
+        for (pos, value) in test.iter_mut().filter(|(pos, _)| pos.x == 1) {
+            *value = pos.x + 1;
+        }
+
+        for (pos, value) in &test {
+            if pos.x == 1 {
+                assert_eq!(*value, 2);
+            } else {
+                assert_eq!(*value, 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_checked() {
+        let mut test : Array2d<usize> = Array2d::new(4, 4);
+        test.set(IVec2 { x : 3, y : 2 }, 7);
+
+        assert_eq!(test.get_checked(IVec2 { x : 3, y : 2 }), Some(&7));
+        assert_eq!(test.get_checked(IVec2 { x : -1, y : 0 }), None);
+        assert_eq!(test.get_checked(IVec2 { x : 0, y : -1 }), None);
+        assert_eq!(test.get_checked(IVec2 { x : 4, y : 0 }), None);
+        assert_eq!(test.get_checked(IVec2 { x : 0, y : 4 }), None);
+    }
+
+    #[test]
+    fn test_get_mut_checked() {
+        let mut test : Array2d<usize> = Array2d::new(4, 4);
+
+        *test.get_mut_checked(IVec2 { x : 1, y : 2 }).unwrap() = 5;
+        assert_eq!(*test.get(IVec2 { x : 1, y : 2 }), 5);
+
+        assert!(test.get_mut_checked(IVec2 { x : -1, y : 0 }).is_none());
+        assert!(test.get_mut_checked(IVec2 { x : 0, y : -1 }).is_none());
+        assert!(test.get_mut_checked(IVec2 { x : 4, y : 0 }).is_none());
+    }
+
+    #[test]
+    fn test_non_square_index_math() {
+        let width = 5;
+        let height = 3;
+
+        assert_eq!(get_1d_from_2d(width, 0, 0), 0);
+        assert_eq!(get_1d_from_2d(width, 4, 0), 4);
+        assert_eq!(get_1d_from_2d(width, 0, 2), 10);
+        assert_eq!(get_1d_from_2d(width, 4, 2), 14);
+        assert!(get_1d_from_2d(width, 4, 2) < width * height);
+
+        assert_eq!(get_2d_from_1d(width, 4), (4, 0));
+        assert_eq!(get_2d_from_1d(width, 10), (0, 2));
+    }
+
+    #[test]
+    fn test_non_square_corners() {
+        let mut test : Array2d<usize> = Array2d::new(5, 3);
+        assert_eq!(test.len(), 15);
+
+        let corners = [
+            IVec2 { x : 0, y : 0 },
+            IVec2 { x : 4, y : 0 },
+            IVec2 { x : 0, y : 2 },
+            IVec2 { x : 4, y : 2 },
+        ];
+
+        for (i, corner) in corners.iter().enumerate() {
+            test.set(*corner, i + 1);
+        }
+
+        for (i, corner) in corners.iter().enumerate() {
+            assert_eq!(*test.get(*corner), i + 1);
+        }
+
+        assert_eq!(test[4], 2);
+        assert_eq!(test[10], 3);
+        assert_eq!(test[14], 4);
+
+        for (pos, value) in &test {
+            if !corners.contains(&pos) {
+                assert_eq!(*value, 0);
+            }
+        }
+
+        assert!(test.get_checked(IVec2 { x : 5, y : 0 }).is_none());
+        assert!(test.get_checked(IVec2 { x : 0, y : 3 }).is_none());
+    }
+
+    #[test]
+    fn test_clone() {
+        let mut test : Array2d<usize> = Array2d::new(3, 3);
+        for i in 0..test.len() {
+            test[i] = i;
+        }
+
+        let copy = test.clone();
+        test.set(IVec2 { x : 1, y : 1 }, 100);
+
+        assert_eq!(copy.len(), 9);
+        assert_eq!(*copy.get(IVec2 { x : 1, y : 1 }), 4);
+        for i in 0..copy.len() {
+            assert_eq!(copy[i], i);
+        }
+    }
+
+    #[test]
+    fn test_debug() {
+        let mut test : Array2d<usize> = Array2d::new(3, 2);
+        test.set(IVec2 { x : 2, y : 1 }, 7);
+
+        let s = format!("{:?}", test);
+        assert!(s.contains("width: 3"));
+        assert!(s.contains("height: 2"));
+        assert!(s.contains("[0, 0, 0]"));
+        assert!(s.contains("[0, 0, 7]"));
+    }
+
+    #[test]
+    fn test_equality() {
+        let mut a : Array2d<usize> = Array2d::new(3, 3);
+        let mut b : Array2d<usize> = Array2d::new(3, 3);
+        assert_eq!(a, b);
+
+        a.set(IVec2 { x : 1, y : 2 }, 1);
+        assert_ne!(a, b);
+
+        b.set(IVec2 { x : 1, y : 2 }, 1);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_equality_swapped_dimensions() {
+        let a : Array2d<usize> = Array2d::new(2, 3);
+        let b : Array2d<usize> = Array2d::new(3, 2);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_from_vec() {
+        let test = Array2d::from_vec(3, 2, vec![0, 1, 2, 3, 4, 5]).unwrap();
+        assert_eq!(test.len(), 6);
+        assert_eq!(*test.get(IVec2 { x : 2, y : 0 }), 2);
+        assert_eq!(*test.get(IVec2 { x : 0, y : 1 }), 3);
+    }
+
+    #[test]
+    fn test_from_vec_size_mismatch() {
+        let r = Array2d::from_vec(3, 2, vec![0, 1, 2, 3, 4]);
+        assert_eq!(r.err(), Some(ArrayError::SizeMismatch { expected: 6, got: 5 }));
+    }
+
+    #[test]
+    fn test_from_fn() {
+        let test = Array2d::from_fn(10, 5, |pos| (pos.x + 1) * (pos.y + 1));
+        assert_eq!(test.len(), 50);
+
+        assert_eq!(*test.get(IVec2 { x : 0, y : 0 }), 1);
+        assert_eq!(*test.get(IVec2 { x : 9, y : 0 }), 10);
+        assert_eq!(*test.get(IVec2 { x : 2, y : 3 }), 12);
+        assert_eq!(*test.get(IVec2 { x : 9, y : 4 }), 50);
+    }
+
+    #[test]
+    fn test_fill() {
+        let mut test : Array2d<usize> = Array2d::new(3, 2);
+        test.fill(9);
+
+        for (_pos, value) in &test {
+            assert_eq!(*value, 9);
+        }
+    }
+
+    #[test]
+    fn test_fill_with() {
+        let mut test : Array2d<String> = Array2d::new(3, 2);
+        test.fill_with(|| String::from("grass"));
+
+        for (_pos, value) in &test {
+            assert_eq!(value, "grass");
+        }
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut test : Array2d<usize> = Array2d::new(3, 2);
+        test.fill(9);
+        test.clear();
+
+        assert_eq!(test.len(), 6);
+        for (_pos, value) in &test {
+            assert_eq!(*value, 0);
+        }
+    }
+
+    #[test]
+    fn test_index_ivec2() {
+        let mut test : Array2d<usize> = Array2d::new(4, 3);
+
+        test[IVec2::new(2, 1)] = 5;
+        assert_eq!(test[IVec2::new(2, 1)], 5);
+        assert_eq!(test[get_1d_from_2d(4, 2, 1)], 5);
+
+        test[get_1d_from_2d(4, 3, 2)] = 8;
+        assert_eq!(test[IVec2::new(3, 2)], 8);
+    }
+
+    #[test]
+    fn test_iter_indexed() {
+        let test : Array2d<usize> = Array2d::from_fn(3, 2, |pos| get_1d_from_2d_ivec2(3, pos));
+
+        let mut expected = 0;
+        for (i, pos, value) in test.iter_indexed() {
+            assert_eq!(i, expected);
+            assert_eq!(pos, get_2d_from_1d_ivec2(3, i));
+            assert_eq!(*value, i);
+            expected += 1;
+        }
+
+        assert_eq!(expected, test.len());
+    }
+
+    #[test]
+    fn test_row() {
+        let test = Array2d::from_fn(4, 3, |pos| get_1d_from_2d_ivec2(4, pos));
+
+        let row : Vec<usize> = test.row(1).copied().collect();
+        assert_eq!(row, vec![4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_column() {
+        let test = Array2d::from_fn(4, 3, |pos| get_1d_from_2d_ivec2(4, pos));
+
+        let column : Vec<usize> = test.column(2).copied().collect();
+        assert_eq!(column, vec![2, 6, 10]);
+    }
+
+    #[test]
+    fn test_rows() {
+        let test = Array2d::from_fn(4, 3, |pos| get_1d_from_2d_ivec2(4, pos));
+
+        let rows : Vec<Vec<usize>> = test.rows().map(|row| row.copied().collect()).collect();
+        assert_eq!(rows, vec![
+            vec![0, 1, 2, 3],
+            vec![4, 5, 6, 7],
+            vec![8, 9, 10, 11],
+        ]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_row_out_of_range() {
+        let test : Array2d<usize> = Array2d::new(4, 3);
+        let _ = test.row(3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_column_out_of_range() {
+        let test : Array2d<usize> = Array2d::new(4, 3);
+        let _ = test.column(-1);
+    }
+
+    #[test]
+    fn test_neighbors4() {
+        let test : Array2d<usize> = Array2d::new(4, 4);
+
+        let corner : Vec<IVec2> = test.neighbors4(IVec2::new(0, 0)).map(|(pos, _)| pos).collect();
+        assert_eq!(corner.len(), 2);
+        assert!(corner.contains(&IVec2::new(1, 0)));
+        assert!(corner.contains(&IVec2::new(0, 1)));
+
+        assert_eq!(test.neighbors4(IVec2::new(1, 1)).count(), 4);
+    }
+
+    #[test]
+    fn test_neighbors8() {
+        let test : Array2d<usize> = Array2d::new(4, 4);
+
+        assert_eq!(test.neighbors8(IVec2::new(0, 0)).count(), 3);
+        assert_eq!(test.neighbors8(IVec2::new(3, 1)).count(), 5);
+
+        let center : Vec<IVec2> = test.neighbors8(IVec2::new(1, 1)).map(|(pos, _)| pos).collect();
+        assert_eq!(center.len(), 8);
+        assert!(!center.contains(&IVec2::new(1, 1)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let test = Array2d::from_fn(3, 2, |pos| pos.x * 10 + pos.y);
+
+        let json = serde_json::to_string(&test).unwrap();
+        let back : Array2d<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(test, back);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_size_mismatch() {
+        let json = r#"{"width":3,"height":2,"data":[1,2,3]}"#;
+        let r : Result<Array2d<i32>, _> = serde_json::from_str(json);
+        assert!(r.is_err());
+    }
+
+    #[test]
+    fn test_reflect() {
+        use bevy::reflect::ReflectRef;
+
+        let test : Array2d<u32> = Array2d::new(3, 2);
+        let ReflectRef::Struct(reflected) = test.reflect_ref() else {
+            panic!("Array2d should reflect as a struct");
+        };
+
+        let width = reflected.field("width").unwrap().downcast_ref::<usize>();
+        let height = reflected.field("height").unwrap().downcast_ref::<usize>();
+        assert_eq!(width, Some(&3));
+        assert_eq!(height, Some(&2));
+    }
+
+    #[test]
+    fn test_component() {
+        #[derive(Resource, Default)]
+        struct Visited(usize);
+
+        fn visit(query : Query<&Array2d<u32>>, mut visited : ResMut<Visited>) {
+            for array in &query {
+                visited.0 += array.len();
+            }
+        }
+
+        let mut app = App::new();
+        app.init_resource::<Visited>();
+        app.add_systems(Update, visit);
+        app.world_mut().spawn(Array2d::<u32>::new(16, 16));
+        app.update();
+
+        assert_eq!(app.world().resource::<Visited>().0, 256);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_iter_mut() {
+        use rayon::prelude::*;
+
+        let mut parallel = Array2d::from_fn(16, 8, |pos| pos.x + pos.y * 16);
+        let mut serial = parallel.clone();
+
+        parallel.par_iter_mut().for_each(|(_pos, value)| *value *= *value);
+        for (_pos, value) in serial.iter_mut() {
+            *value *= *value;
+        }
+
+        assert_eq!(parallel, serial);
+
+        parallel.par_iter_mut().for_each(|(pos, value)| *value = pos.x - pos.y);
+        for (pos, value) in &parallel {
+            assert_eq!(*value, pos.x - pos.y);
+        }
+    }
+
+    #[test]
+    fn test_swap() {
+        let mut test : Array2d<usize> = Array2d::new(4, 3);
+        let a = IVec2::new(0, 0);
+        let b = IVec2::new(3, 2);
+        test.set(a, 1);
+        test.set(b, 2);
+
+        test.swap(a, b);
+        assert_eq!(*test.get(a), 2);
+        assert_eq!(*test.get(b), 1);
+    }
+
+    #[test]
+    fn test_resize_preserving() {
+        let mut test = Array2d::from_fn(2, 2, |pos| pos.x + pos.y * 10 + 1);
+        test.resize_preserving(3, 3);
+        assert_eq!(test.len(), 9);
+
+        for (pos, value) in &test {
+            if pos.x < 2 && pos.y < 2 {
+                assert_eq!(*value, pos.x + pos.y * 10 + 1);
+            } else {
+                assert_eq!(*value, 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_resize_preserving_shrink() {
+        let mut test = Array2d::from_fn(3, 3, |pos| pos.x + pos.y * 10 + 1);
+        test.resize_preserving(2, 1);
+        assert_eq!(test.len(), 2);
+        assert_eq!(*test.get(IVec2::new(0, 0)), 1);
+        assert_eq!(*test.get(IVec2::new(1, 0)), 2);
+    }
+
+    #[test]
+    fn test_get_wrapped() {
+        let test = Array2d::from_fn(4, 3, |pos| pos);
+
+        assert_eq!(*test.get_wrapped(IVec2::new(-1, -1)), IVec2::new(3, 2));
+        assert_eq!(*test.get_wrapped(IVec2::new(4, 3)), IVec2::new(0, 0));
+        assert_eq!(*test.get_wrapped(IVec2::new(-5, 7)), IVec2::new(3, 1));
+    }
+
+    #[test]
+    fn test_set_wrapped() {
+        let mut test : Array2d<usize> = Array2d::new(4, 3);
+
+        test.set_wrapped(IVec2::new(-1, 0), 1);
+        assert_eq!(*test.get(IVec2::new(3, 0)), 1);
+
+        *test.get_wrapped_mut(IVec2::new(5, -1)) = 2;
+        assert_eq!(*test.get(IVec2::new(1, 2)), 2);
+    }
+
+    #[test]
+    fn test_get_clamped() {
+        let test = Array2d::from_fn(4, 4, |pos| pos);
+
+        assert_eq!(*test.get_clamped(IVec2::new(-5, 100)), IVec2::new(0, 3));
+        assert_eq!(*test.get_clamped(IVec2::new(2, 1)), IVec2::new(2, 1));
+        assert_eq!(*test.get_clamped(IVec2::new(9, -1)), IVec2::new(3, 0));
+    }
+
+    #[test]
+    fn test_default() {
+        let test = Array2d::<u8>::default();
+        assert_eq!(test.len(), 1);
+        assert_eq!(test[0], 0);
+    }
+
+    #[test]
+    fn test_map() {
+        let test = Array2d::from_fn(3, 2, |pos| get_1d_from_2d_ivec2(3, pos));
+        let squares = test.map(|_pos, value| (value * value) as u64);
+
+        assert_eq!(squares.len(), 6);
+        for (pos, value) in &squares {
+            let i = get_1d_from_2d_ivec2(3, pos) as u64;
+            assert_eq!(*value, i * i);
+        }
+    }
+
+    #[test]
+    fn test_positions() {
+        let mut test : Array2d<usize> = Array2d::new(2, 2);
+        let positions : Vec<IVec2> = test.positions().collect();
+
+        // The positions do not borrow the array.
+        for pos in &positions {
+            test.set(*pos, 1);
+        }
+
+        assert_eq!(positions.len(), 4);
+        for pos in [IVec2::new(0, 0), IVec2::new(1, 0), IVec2::new(0, 1), IVec2::new(1, 1)] {
+            assert_eq!(positions.iter().filter(|p| **p == pos).count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_exact_size_iter() {
+        let mut test : Array2d<usize> = Array2d::new(3, 2);
+
+        let mut iter = test.iter();
+        assert_eq!(iter.len(), test.len());
+        iter.next();
+        assert_eq!(iter.len(), 5);
+        iter.by_ref().for_each(drop);
+        assert_eq!(iter.len(), 0);
+
+        let mut iter_mut = test.iter_mut();
+        assert_eq!(iter_mut.size_hint(), (6, Some(6)));
+        iter_mut.next();
+        assert_eq!(iter_mut.len(), 5);
+    }
+
+    #[test]
+    fn test_reverse_iter() {
+        let test = Array2d::from_fn(3, 2, |pos| pos.x * 10 + pos.y);
+
+        let mut forward : Vec<(IVec2, &i32)> = test.iter().collect();
+        forward.reverse();
+        let backward : Vec<(IVec2, &i32)> = test.iter().rev().collect();
+        assert_eq!(forward, backward);
+        assert_eq!(backward[0].0, IVec2::new(2, 1));
+
+        let mut iter = test.iter();
+        assert_eq!(iter.next().unwrap().0, IVec2::new(0, 0));
+        assert_eq!(iter.next_back().unwrap().0, IVec2::new(2, 1));
+        assert_eq!(iter.len(), 4);
+    }
+
+    #[test]
+    fn test_sub_region() {
+        let test = Array2d::from_fn(4, 4, |pos| pos);
+        let region = test.sub_region(IVec2::new(1, 2), IVec2::new(2, 2)).unwrap();
+
+        assert_eq!(region.len(), 4);
+        assert_eq!(*region.get(IVec2::new(0, 0)), IVec2::new(1, 2));
+        assert_eq!(*region.get(IVec2::new(1, 0)), IVec2::new(2, 2));
+        assert_eq!(*region.get(IVec2::new(0, 1)), IVec2::new(1, 3));
+        assert_eq!(*region.get(IVec2::new(1, 1)), IVec2::new(2, 3));
+    }
+
+    #[test]
+    fn test_sub_region_out_of_bounds() {
+        let test : Array2d<usize> = Array2d::new(4, 4);
+
+        let r = test.sub_region(IVec2::new(3, 3), IVec2::new(2, 2));
+        assert_eq!(r.err(), Some(ArrayError::OutOfBounds { pos: IVec2::new(4, 4), dims: IVec2::new(4, 4) }));
+
+        let r = test.sub_region(IVec2::new(1, 1), IVec2::new(0, 2));
+        assert_eq!(r.err(), Some(ArrayError::ZeroDimension));
+
+        let r = test.sub_region(IVec2::new(1, 1), IVec2::new(-1, 2));
+        assert!(matches!(r, Err(ArrayError::OutOfBounds { .. })));
+    }
+
+    #[test]
+    fn test_blit() {
+        let mut test : Array2d<usize> = Array2d::new(4, 4);
+        let stamp = Array2d::from_fn(2, 2, |pos| get_1d_from_2d_ivec2(2, pos) + 1);
+
+        test.blit(IVec2::new(3, -1), &stamp);
+
+        for (pos, value) in &test {
+            if pos == IVec2::new(3, 0) {
+                assert_eq!(*value, 3);
+            } else {
+                assert_eq!(*value, 0);
+            }
+        }
+
+        test.blit(IVec2::new(1, 1), &stamp);
+        assert_eq!(*test.get(IVec2::new(1, 1)), 1);
+        assert_eq!(*test.get(IVec2::new(2, 2)), 4);
+    }
+
+    #[test]
+    fn test_transpose() {
+        let test = Array2d::from_fn(3, 2, |pos| pos.x * 10 + pos.y);
+        let transposed = test.transpose();
+
+        assert_eq!(transposed.len(), 6);
+        assert!(transposed.get_checked(IVec2::new(1, 2)).is_some());
+        assert!(transposed.get_checked(IVec2::new(2, 0)).is_none());
+
+        for (pos, value) in &test {
+            assert_eq!(transposed.get(IVec2::new(pos.y, pos.x)), value);
+        }
+    }
+
+    #[test]
+    fn test_rotate_cw() {
+        // a b c
+        // d e f
+        let test = Array2d::from_vec(3, 2, vec!['a', 'b', 'c', 'd', 'e', 'f']).unwrap();
+
+        // d a
+        // e b
+        // f c
+        let expected = Array2d::from_vec(2, 3, vec!['d', 'a', 'e', 'b', 'f', 'c']).unwrap();
+        assert_eq!(test.rotate_cw(), expected);
+    }
+
+    #[test]
+    fn test_rotate_ccw() {
+        let test = Array2d::from_vec(3, 2, vec!['a', 'b', 'c', 'd', 'e', 'f']).unwrap();
+
+        // c f
+        // b e
+        // a d
+        let expected = Array2d::from_vec(2, 3, vec!['c', 'f', 'b', 'e', 'a', 'd']).unwrap();
+        assert_eq!(test.rotate_ccw(), expected);
+        assert_eq!(test.rotate_ccw().rotate_cw(), test);
+    }
+
+    #[test]
+    fn test_rotate_full_turn() {
+        let test = Array2d::from_fn(4, 3, |pos| pos.x * 10 + pos.y);
+        let rotated = test.rotate_cw().rotate_cw().rotate_cw().rotate_cw();
+        assert_eq!(rotated, test);
+    }
+
+    #[test]
+    fn test_flip_x() {
+        let mut test = Array2d::from_vec(3, 3, (0..9).collect()).unwrap();
+        test.flip_x();
+
+        let expected = Array2d::from_vec(3, 3, vec![2, 1, 0, 5, 4, 3, 8, 7, 6]).unwrap();
+        assert_eq!(test, expected);
+
+        test.flip_x();
+        assert_eq!(test, Array2d::from_vec(3, 3, (0..9).collect()).unwrap());
+    }
+
+    #[test]
+    fn test_flip_y() {
+        let mut test = Array2d::from_vec(3, 3, (0..9).collect()).unwrap();
+        test.flip_y();
+
+        let expected = Array2d::from_vec(3, 3, vec![6, 7, 8, 3, 4, 5, 0, 1, 2]).unwrap();
+        assert_eq!(test, expected);
+
+        test.flip_y();
+        assert_eq!(test, Array2d::from_vec(3, 3, (0..9).collect()).unwrap());
+    }
+
+    #[test]
+    fn test_count_where() {
+        let mut test : Array2d<usize> = Array2d::new(4, 4);
+        test.set(IVec2::new(0, 0), 1);
+        test.set(IVec2::new(3, 1), 2);
+        test.set(IVec2::new(2, 3), 3);
+
+        assert_eq!(test.count_where(|value| *value != 0), 3);
+        assert_eq!(test.count_where(|value| *value > 1), 2);
+    }
+
+    #[test]
+    fn test_find_position() {
+        let mut test : Array2d<usize> = Array2d::new(4, 3);
+        test.set(IVec2::new(2, 1), 42);
+        test.set(IVec2::new(3, 2), 7);
+
+        assert_eq!(test.find_position(|value| *value == 42), Some(IVec2::new(2, 1)));
+        assert_eq!(test.find_position(|value| *value != 0), Some(IVec2::new(2, 1)));
+    }
+
+    #[test]
+    fn test_find_position_none() {
+        let test : Array2d<usize> = Array2d::new(4, 3);
+        assert_eq!(test.find_position(|value| *value == 42), None);
+    }
+
+    #[test]
+    fn test_contains_point() {
+        let test : Array2d<usize> = Array2d::new(5, 3);
+
+        assert!(test.contains_point(IVec2::new(0, 0)));
+        assert!(test.contains_point(IVec2::new(4, 2)));
+        assert!(!test.contains_point(IVec2::new(-1, 0)));
+        assert!(!test.contains_point(IVec2::new(0, -1)));
+        assert!(!test.contains_point(IVec2::new(5, 0)));
+        assert!(!test.contains_point(IVec2::new(0, 3)));
+    }
+
+    #[test]
+    fn test_dimensions() {
+        let test : Array2d<usize> = Array2d::new(5, 3);
+        assert_eq!(test.width(), 5);
+        assert_eq!(test.height(), 3);
+        assert_eq!(test.dimensions(), IVec2::new(5, 3));
+    }
+
+    #[test]
+    fn test_from_iter_flat() {
+        let test = Array2d::from_iter_flat(2, 2, 0..4).unwrap();
+        assert_eq!(*test.get(IVec2::new(0, 0)), 0);
+        assert_eq!(*test.get(IVec2::new(1, 0)), 1);
+        assert_eq!(*test.get(IVec2::new(0, 1)), 2);
+        assert_eq!(*test.get(IVec2::new(1, 1)), 3);
+    }
+
+    #[test]
+    fn test_from_iter_flat_size_mismatch() {
+        let too_few = Array2d::from_iter_flat(2, 2, 0..3);
+        assert_eq!(too_few.err(), Some(ArrayError::SizeMismatch { expected: 4, got: 3 }));
+
+        let too_many = Array2d::from_iter_flat(2, 2, 0..6);
+        assert_eq!(too_many.err(), Some(ArrayError::TooManyItems { expected: 4 }));
+
+        let unbounded = Array2d::from_iter_flat(2, 2, 0..);
+        assert_eq!(unbounded.err(), Some(ArrayError::TooManyItems { expected: 4 }));
+    }
+
+    #[test]
+    fn test_as_slice() {
+        let mut test = Array2d::from_fn(3, 2, |pos| pos.x + pos.y * 10);
+        assert_eq!(test.as_slice().len(), test.len());
+        assert_eq!(test.as_slice()[get_1d_from_2d(3, 2, 1)], 12);
+
+        test.as_mut_slice()[get_1d_from_2d(3, 1, 1)] = 99;
+        assert_eq!(*test.get(IVec2::new(1, 1)), 99);
+    }
+
+    #[test]
+    fn test_into_vec() {
+        let data : Vec<usize> = (0..6).collect();
+        let test = Array2d::from_vec(3, 2, data.clone()).unwrap();
+        assert_eq!(test.into_vec(), data);
+    }
+
+    #[test]
+    fn test_screen_position_round_trip() {
+        let grid_size = 64.0;
+        let mut test : Array2d<usize> = Array2d::new(5, 3);
+
+        // A click in the fourth column of the second row.
+        let click = Vec2::new(3.0 * grid_size + 10.0, grid_size + 20.0);
+        let cell = map_to_grid_vec2(click, grid_size);
+        assert_eq!(cell, IVec2::new(3, 1));
+
+        test.set(cell, 1);
+        assert_eq!(*test.get(cell), 1);
+        assert_eq!(test.as_slice()[get_1d_from_2d(5, 3, 1)], 1);
+
+        let found : Vec<IVec2> = test.iter().filter(|(_, v)| **v == 1).map(|(pos, _)| pos).collect();
+        assert_eq!(found, vec![cell]);
+    }
+
+    #[test]
+    fn test_map_with_offset() {
+        let (cell, offset) = map_to_grid_with_offset(Vec2::new(96.0, 160.0), 64.0);
+        assert_eq!(cell, IVec2::new(1, 2));
+        assert_eq!(offset, Vec2::new(32.0, 32.0));
+
+        let (cell, offset) = map_to_grid_with_offset(Vec2::new(-16.0, 8.0), 64.0);
+        assert_eq!(cell, IVec2::new(-1, 0));
+        assert_eq!(offset, Vec2::new(48.0, 8.0));
+    }
+
+    #[test]
+    fn test_map_nonuniform() {
+        let cell = Vec2::new(64.0, 32.0);
+
+        assert_eq!(map_to_grid_vec2_nonuniform(Vec2::new(63.0, 63.0), cell), IVec2::new(0, 1));
+        assert_eq!(map_to_grid_vec2_nonuniform(Vec2::new(64.0, 31.0), cell), IVec2::new(1, 0));
+        assert_eq!(map_to_grid_vec2_nonuniform(Vec2::new(130.0, 96.0), cell), IVec2::new(2, 3));
+        assert_eq!(map_to_grid_vec2_nonuniform(Vec2::new(-1.0, -1.0), cell), IVec2::new(-1, -1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_map_nonuniform_zero_cell() {
+        map_to_grid_vec2_nonuniform(Vec2::new(1.0, 1.0), Vec2::new(64.0, 0.0));
+    }
+
+    #[test]
+    fn test_map_with_origin() {
+        let origin = Vec2::new(100.0, 100.0);
+
+        assert_eq!(map_to_grid_vec2_offset(Vec2::new(100.0, 100.0), 64.0, origin), IVec2::new(0, 0));
+        assert_eq!(map_to_grid_vec2_offset(Vec2::new(230.0, 170.0), 64.0, origin), IVec2::new(2, 1));
+        assert_eq!(map_to_grid_vec2_offset(Vec2::new(99.0, 100.0), 64.0, origin), IVec2::new(-1, 0));
+        assert_eq!(map_to_grid_vec2_offset(Vec2::new(0.0, 30.0), 64.0, origin), IVec2::new(-2, -2));
+    }
+
+    #[test]
+    fn test_get_many_mut() {
+        let mut test : Array2d<usize> = Array2d::new(4, 4);
+        let positions = [IVec2::new(0, 0), IVec2::new(3, 1), IVec2::new(2, 3)];
+
+        let [a, b, c] = test.get_many_mut(positions).unwrap();
+        *a = 1;
+        *b = 2;
+        *c = *a + *b;
+
+        assert_eq!(*test.get(positions[0]), 1);
+        assert_eq!(*test.get(positions[1]), 2);
+        assert_eq!(*test.get(positions[2]), 3);
+    }
+
+    #[test]
+    fn test_get_many_mut_invalid() {
+        let mut test : Array2d<usize> = Array2d::new(4, 4);
+
+        assert!(test.get_many_mut([IVec2::new(1, 1), IVec2::new(1, 1)]).is_none());
+        assert!(test.get_many_mut([IVec2::new(1, 1), IVec2::new(4, 1)]).is_none());
+        assert!(test.get_many_mut([IVec2::new(-1, 1)]).is_none());
+    }
+
+    #[test]
+    fn test_try_set() {
+        let mut test : Array2d<usize> = Array2d::new(4, 3);
+
+        assert_eq!(test.try_set(IVec2::new(3, 2), 5), Ok(()));
+        assert_eq!(*test.get(IVec2::new(3, 2)), 5);
+
+        let r = test.try_set(IVec2::new(4, 0), 1);
+        assert_eq!(r, Err(ArrayError::OutOfBounds { pos: IVec2::new(4, 0), dims: IVec2::new(4, 3) }));
+
+        let r = test.try_set(IVec2::new(0, -1), 1);
+        assert_eq!(r, Err(ArrayError::OutOfBounds { pos: IVec2::new(0, -1), dims: IVec2::new(4, 3) }));
+    }
+
+    #[test]
+    fn test_to_array3d() {
+        let test = Array2d::from_fn(4, 3, |pos| pos.x + pos.y * 10);
+        let copy = test.clone();
+        let slab = test.to_array3d();
+
+        assert_eq!(slab.dimensions(), IVec3::new(4, 3, 1));
+        for (pos, value) in &copy {
+            assert_eq!(slab.get(pos.extend(0)), value);
+        }
+    }
+
+    #[test]
+    fn test_stride() {
+        let mut array : Array2d<i32> = Array2d::new_with_stride(3, 2, 4);
+        for (pos, value) in array.iter_mut() {
+            *value = pos.y * 10 + pos.x + 1;
+        }
+
+        assert_eq!(array.len(), 6);
+        assert_eq!(array.stride(), 4);
+        assert_eq!(array.as_slice().len(), array.stride() * array.height());
+        assert_eq!(&array.as_slice()[0..3], &[1, 2, 3]);
+        assert_eq!(&array.as_slice()[4..7], &[11, 12, 13]);
+
+        assert_eq!(*array.get(IVec2::new(0, 1)), 11);
+        assert_eq!(array[3], 11);
+        assert_eq!(array.iter().count(), 6);
+        assert_eq!(array.row(1).copied().collect::<Vec<_>>(), vec![11, 12, 13]);
+        assert_eq!(array.column(2).copied().collect::<Vec<_>>(), vec![3, 13]);
+        assert_eq!(array.count_where(|value| *value == 0), 0);
+
+        let dense = Array2d::from_vec(3, 2, vec![1, 2, 3, 11, 12, 13]).unwrap();
+        assert_eq!(array, dense);
+        assert_eq!(array.into_vec(), vec![1, 2, 3, 11, 12, 13]);
+    }
+
+    #[test]
+    fn test_chunks() {
+        let array = Array2d::from_fn(4, 4, |pos| pos.y * 4 + pos.x);
+        let chunks : Vec<_> = array.chunks(IVec2::new(2, 2)).collect();
+
+        assert_eq!(chunks.len(), 4);
+        assert_eq!(chunks[1].0, IVec2::new(2, 0));
+        assert_eq!(chunks[1].1, Array2d::from_vec(2, 2, vec![2, 3, 6, 7]).unwrap());
+        assert_eq!(chunks[2].0, IVec2::new(0, 2));
+
+        let array = Array2d::from_fn(5, 4, |pos| pos.y * 5 + pos.x);
+        let chunks : Vec<_> = array.chunks(IVec2::new(2, 2)).collect();
+
+        assert_eq!(chunks.len(), 6);
+        assert_eq!(chunks[2].0, IVec2::new(4, 0));
+        assert_eq!(chunks[2].1.dimensions(), IVec2::new(1, 2));
+        assert_eq!(chunks[2].1.as_slice(), &[4, 9]);
+        assert_eq!(chunks[5].1.dimensions(), IVec2::new(1, 2));
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        // A wall of ones encloses the center cell.
+        let mut array = Array2d::from_vec(5, 5, vec![
+            0, 0, 0, 0, 0,
+            0, 1, 1, 1, 0,
+            0, 1, 0, 1, 0,
+            0, 1, 1, 1, 0,
+            0, 0, 0, 0, 0,
+        ]).unwrap();
+
+        array.flood_fill(IVec2::new(0, 0), 2);
+        assert_eq!(array.count_where(|value| *value == 2), 16);
+        assert_eq!(*array.get(IVec2::new(2, 2)), 0);
+        assert_eq!(array.count_where(|value| *value == 1), 8);
+
+        array.flood_fill(IVec2::new(2, 2), 3);
+        assert_eq!(*array.get(IVec2::new(2, 2)), 3);
+        assert_eq!(array.count_where(|value| *value == 3), 1);
+
+        let before = array.clone();
+        array.flood_fill(IVec2::new(1, 1), 1);
+        assert_eq!(array, before);
+    }
+
+    #[test]
+    fn test_draw_line() {
+        fn touched(a : IVec2, b : IVec2) -> Vec<IVec2> {
+            let mut array : Array2d<bool> = Array2d::new(5, 5);
+            array.draw_line(a, b, true);
+            array.iter().filter(|(_, value)| **value).map(|(pos, _)| pos).collect()
+        }
+
+        assert_eq!(
+            touched(IVec2::new(1, 2), IVec2::new(3, 2)),
+            vec![IVec2::new(1, 2), IVec2::new(2, 2), IVec2::new(3, 2)]
+        );
+        assert_eq!(
+            touched(IVec2::new(4, 3), IVec2::new(4, 1)),
+            vec![IVec2::new(4, 1), IVec2::new(4, 2), IVec2::new(4, 3)]
+        );
+        assert_eq!(
+            touched(IVec2::new(0, 0), IVec2::new(2, 2)),
+            vec![IVec2::new(0, 0), IVec2::new(1, 1), IVec2::new(2, 2)]
+        );
+
+        // A steep line advances y every step and x every other step.
+        assert_eq!(
+            touched(IVec2::new(0, 0), IVec2::new(2, 4)),
+            vec![IVec2::new(0, 0), IVec2::new(1, 1), IVec2::new(1, 2), IVec2::new(2, 3), IVec2::new(2, 4)]
+        );
+
+        // Cells outside of the array are skipped.
+        assert_eq!(touched(IVec2::new(-2, 0), IVec2::new(1, 0)), vec![IVec2::new(0, 0), IVec2::new(1, 0)]);
+    }
+
+    #[test]
+    fn test_rect() {
+        let mut filled : Array2d<i32> = Array2d::new(5, 5);
+        filled.fill_rect(IVec2::new(1, 1), IVec2::new(3, 3), 1);
+
+        let mut outlined : Array2d<i32> = Array2d::new(5, 5);
+        outlined.draw_rect_outline(IVec2::new(1, 1), IVec2::new(3, 3), 1);
+
+        for (pos, value) in &filled {
+            let inside = pos.cmpge(IVec2::ONE).all() && pos.cmple(IVec2::splat(3)).all();
+            assert_eq!(*value, inside as i32);
+
+            let border = inside && pos != IVec2::splat(2);
+            assert_eq!(*outlined.get(pos), border as i32);
+        }
+
+        // Both helpers clip to the array.
+        let mut array : Array2d<i32> = Array2d::new(5, 5);
+        array.fill_rect(IVec2::new(3, -1), IVec2::new(4, 3), 1);
+        assert_eq!(array.count_where(|value| *value == 1), 4);
+
+        let mut array : Array2d<i32> = Array2d::new(5, 5);
+        array.draw_rect_outline(IVec2::new(-1, -1), IVec2::new(3, 3), 1);
+        assert_eq!(array.count_where(|value| *value == 1), 3);
+    }
+
+    #[test]
+    fn test_convolve3x3() {
+        let mut array : Array2d<f32> = Array2d::new(5, 5);
+        array.set(IVec2::new(2, 2), 9.0);
+
+        let blurred = array.convolve3x3([[1.0 / 9.0; 3]; 3]);
+        for (pos, value) in &blurred {
+            let near = (pos - IVec2::splat(2)).abs().max_element() <= 1;
+            let expected = if near { 1.0 } else { 0.0 };
+            assert!((*value - expected).abs() < 1e-5);
+        }
+
+        // Edges are clamped, so a constant array stays constant.
+        let mut array : Array2d<f32> = Array2d::new(3, 3);
+        array.fill(2.0);
+        let blurred = array.convolve3x3([[1.0 / 9.0; 3]; 3]);
+        assert!(blurred.iter().all(|(_, value)| (*value - 2.0).abs() < 1e-5));
+    }
+
+    #[test]
+    fn test_sample_bilinear() {
+        let array = Array2d::from_vec(2, 2, vec![0.0f32, 2.0, 4.0, 6.0]).unwrap();
+
+        for (pos, value) in &array {
+            let center = pos.as_vec2() + Vec2::splat(0.5);
+            assert_eq!(array.sample_bilinear(center), *value);
+        }
+
+        assert_eq!(array.sample_bilinear(Vec2::new(1.0, 0.5)), 1.0);
+        assert_eq!(array.sample_bilinear(Vec2::new(0.5, 1.0)), 2.0);
+        assert_eq!(array.sample_bilinear(Vec2::new(1.0, 1.0)), 3.0);
+
+        // Positions outside of the cell centers are clamped.
+        assert_eq!(array.sample_bilinear(Vec2::new(-3.0, 0.0)), 0.0);
+        assert_eq!(array.sample_bilinear(Vec2::new(5.0, 5.0)), 6.0);
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut array : Array2d<i32> = Array2d::new(3, 3);
+        array.fill(7);
+        array.retain(|pos, _| pos.x == pos.y);
+
+        for (pos, value) in &array {
+            assert_eq!(*value, if pos.x == pos.y { 7 } else { 0 });
+        }
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut array : Array2d<i32> = Array2d::new_with_stride(3, 2, 4);
+        array.apply(|pos, value| *value = pos.x * 10 + pos.y);
 
-        for i in 0..test.len() {
-            test[i] = i;
-            let comp = test[i];
+        assert_eq!(array.into_vec(), vec![0, 10, 20, 1, 11, 21]);
+    }
 
-            assert_eq!(i, comp);
+    #[test]
+    fn test_distance_field() {
+        let mut array : Array2d<bool> = Array2d::new(7, 7);
+        array.set(IVec2::new(3, 3), true);
+
+        let field = array.distance_field(|value| *value);
+        assert_eq!(*field.get(IVec2::new(3, 3)), 0.0);
+        assert_eq!(*field.get(IVec2::new(4, 3)), 1.0);
+        assert_eq!(*field.get(IVec2::new(3, 0)), 3.0);
+        assert!((*field.get(IVec2::new(4, 4)) - std::f32::consts::SQRT_2).abs() < 1e-5);
+
+        // Distances grow from the center towards the corners.
+        for step in 0..3 {
+            let inner = *field.get(IVec2::splat(3 + step));
+            let outer = *field.get(IVec2::splat(4 + step));
+            assert!(outer > inner);
         }
+
+        let empty : Array2d<bool> = Array2d::new(2, 2);
+        assert!(empty.distance_field(|value| *value).iter().all(|(_, value)| value.is_infinite()));
     }
 
     #[test]
-    fn test_resize_array() {
-        let mut test : Array2d<usize> = Array2d::new(2, 2);
+    fn test_find_path() {
+        fn assert_connected(path : &[IVec2]) {
+            for step in path.windows(2) {
+                assert_eq!((step[1] - step[0]).abs().element_sum(), 1);
+            }
+        }
+
+        let open : Array2d<bool> = Array2d::new(5, 5);
+        let path = open.find_path(IVec2::new(0, 0), IVec2::new(4, 3), |wall| !*wall).unwrap();
+        assert_eq!(path.len(), 8);
+        assert_eq!(path[0], IVec2::new(0, 0));
+        assert_eq!(path[7], IVec2::new(4, 3));
+        assert_connected(&path);
+
+        // A wall in column 2 leaves a single gap in the bottom row.
+        let mut walled : Array2d<bool> = Array2d::new(5, 5);
+        walled.draw_line(IVec2::new(2, 0), IVec2::new(2, 3), true);
+        let path = walled.find_path(IVec2::new(0, 0), IVec2::new(4, 0), |wall| !*wall).unwrap();
+        assert_eq!(path.len(), 13);
+        assert!(path.contains(&IVec2::new(2, 4)));
+        assert!(path.iter().all(|pos| !*walled.get(*pos)));
+        assert_connected(&path);
+
+        walled.set(IVec2::new(2, 4), true);
+        assert_eq!(walled.find_path(IVec2::new(0, 0), IVec2::new(4, 0), |wall| !*wall), None);
+        assert_eq!(walled.find_path(IVec2::new(0, 0), IVec2::new(2, 0), |wall| !*wall), None);
+    }
+
+    #[test]
+    fn test_set_region() {
+        let mut array : Array2d<i32> = Array2d::new(4, 4);
+        array.set_region(IVec2::new(1, 1), IVec2::new(2, 2), &[1, 2, 3, 4]).unwrap();
+
+        assert_eq!(array.as_slice(), &[
+            0, 0, 0, 0,
+            0, 1, 2, 0,
+            0, 3, 4, 0,
+            0, 0, 0, 0,
+        ]);
+
+        let before = array.clone();
+        let error = array.set_region(IVec2::new(3, 0), IVec2::new(2, 2), &[5, 6, 7, 8]).unwrap_err();
+        assert_eq!(error, ArrayError::OutOfBounds { pos: IVec2::new(4, 1), dims: IVec2::new(4, 4) });
+        assert_eq!(array, before);
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut array : Array2d<i32> = Array2d::new(0, 0);
+        assert!(array.is_empty());
+        assert_eq!(array.len(), 0);
+        assert_eq!(array.iter().count(), 0);
+        assert_eq!(array.iter_mut().count(), 0);
+        assert_eq!(array.rows().count(), 0);
+        assert_eq!(array.get_checked(IVec2::ZERO), None);
+        assert_eq!(array.find_position(|_| true), None);
+        assert_eq!(array, Array2d::from_vec(0, 0, Vec::new()).unwrap());
+        assert!(!format!("{:?}", array).is_empty());
+
+        array.resize(2, 3);
+        assert!(!array.is_empty());
+        assert_eq!(array.len(), 6);
+
+        array.resize(0, 3);
+        assert!(array.is_empty());
+        assert_eq!(array.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_shrink_to_fit() {
+        let mut array : Array2d<u8> = Array2d::new(64, 64);
+        array.resize(2, 2);
+        assert_eq!(array.as_slice().len(), 4);
+
+        array.shrink_to_fit();
+        let capacity = array.into_vec().capacity();
+        assert!(capacity < 64 * 64);
+    }
+
+    #[test]
+    fn test_reserve() {
+        let mut array : Array2d<u8> = Array2d::new(2, 2);
+        array.reserve(60);
+        assert_eq!(array.len(), 4);
+        assert_eq!(array.dimensions(), IVec2::new(2, 2));
+
+        let capacity = array.into_vec().capacity();
+        assert!(capacity >= 64);
+    }
+
+    #[test]
+    fn test_hash() {
+        use std::hash::{BuildHasher, RandomState};
+
+        let state = RandomState::new();
+        let a = Array2d::from_fn(3, 2, |pos| pos.x + pos.y);
+        let mut b : Array2d<i32> = Array2d::new_with_stride(3, 2, 4);
+        b.apply(|pos, value| *value = pos.x + pos.y);
+
+        assert_eq!(a, b);
+        assert_eq!(state.hash_one(&a), state.hash_one(&b));
+
+        let c = Array2d::from_fn(2, 3, |pos| pos.x + pos.y);
+        assert_ne!(state.hash_one(&a), state.hash_one(&c));
+    }
+
+    #[test]
+    fn test_hash_set() {
+        use std::collections::HashSet;
+
+        let mut set = HashSet::new();
+        set.insert(Array2d::from_fn(3, 2, |pos| pos.x));
+        set.insert(Array2d::from_fn(3, 2, |pos| pos.x));
+        set.insert(Array2d::from_fn(3, 2, |pos| pos.y));
+
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_ord() {
+        let mut arrays : Vec<Array2d<u8>> = vec![
+            Array2d::from_vec(2, 1, vec![9, 9]).unwrap(),
+            Array2d::from_vec(1, 2, vec![5, 5]).unwrap(),
+            Array2d::from_vec(2, 1, vec![1, 2]).unwrap(),
+            Array2d::from_vec(1, 1, vec![7]).unwrap(),
+        ];
+        arrays.sort();
+
+        let sorted : Vec<Vec<u8>> = arrays.into_iter().map(|array| array.into_vec()).collect();
+        assert_eq!(sorted, vec![vec![7], vec![5, 5], vec![1, 2], vec![9, 9]]);
+
+        let a = Array2d::from_vec(1, 1, vec![1.0f32]).unwrap();
+        let b = Array2d::from_vec(1, 1, vec![f32::NAN]).unwrap();
+        assert_eq!(a.partial_cmp(&b), None);
+    }
+
+    #[test]
+    fn test_from_normalized() {
+        let gradient = Array2d::from_normalized(5, 3, |x, y| x * y);
+        assert_eq!(*gradient.get(IVec2::new(0, 0)), 0.0);
+        assert_eq!(*gradient.get(IVec2::new(4, 0)), 0.0);
+        assert_eq!(*gradient.get(IVec2::new(4, 2)), 1.0);
+        assert_eq!(*gradient.get(IVec2::new(2, 1)), 0.25);
+
+        let column = Array2d::from_normalized(1, 3, |x, y| (x, y));
+        assert_eq!(*column.get(IVec2::new(0, 2)), (0.0, 1.0));
+    }
+
+    #[test]
+    fn test_moore_stencil() {
+        let test = Array2d::from_fn(3, 3, |pos| pos.y * 3 + pos.x);
+        let border = -1;
+
+        let stencil = test.moore_stencil(IVec2::new(0, 0), &border);
+        assert_eq!(stencil.iter().filter(|value| std::ptr::eq(**value, &border)).count(), 5);
+        assert_eq!(stencil.map(|value| *value), [-1, -1, -1, -1, 0, 1, -1, 3, 4]);
+
+        let center = test.moore_stencil(IVec2::new(1, 1), &border);
+        assert_eq!(center.map(|value| *value), [0, 1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_step_cellular() {
+        fn life(alive : &bool, neighbors : &[&bool]) -> bool {
+            let count = neighbors.iter().filter(|value| ***value).count();
+            count == 3 || (*alive && count == 2)
+        }
+
+        let mut blinker : Array2d<bool> = Array2d::new(5, 5);
+        blinker.draw_line(IVec2::new(1, 2), IVec2::new(3, 2), true);
+
+        let mut vertical : Array2d<bool> = Array2d::new(5, 5);
+        vertical.draw_line(IVec2::new(2, 1), IVec2::new(2, 3), true);
+
+        let next = blinker.step_cellular(life);
+        assert_eq!(next, vertical);
+        assert_eq!(next.step_cellular(life), blinker);
+    }
+
+    #[test]
+    fn test_windows() {
+        let test = Array2d::from_fn(5, 5, |pos| pos.y * 5 + pos.x);
+        let windows : Vec<_> = test.windows(IVec2::new(3, 3)).collect();
+
+        assert_eq!(windows.len(), 9);
+        assert_eq!(windows[1].0, IVec2::new(1, 0));
+        assert_eq!(windows[1].1, vec![&1, &2, &3, &6, &7, &8, &11, &12, &13]);
+        assert_eq!(windows[8].0, IVec2::new(2, 2));
+
+        assert_eq!(test.windows(IVec2::new(6, 1)).count(), 0);
+    }
+
+    #[test]
+    fn test_iter_mut_every_cell() {
+        let mut test : Array2d<i32> = Array2d::new_with_stride(3, 3, 5);
+        let refs : Vec<(IVec2, &mut i32)> = test.iter_mut().collect();
+        assert_eq!(refs.len(), 9);
+
+        for (pos, value) in refs {
+            *value = pos.y * 3 + pos.x;
+        }
+
+        assert_eq!(test.into_vec(), (0..9).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_row_slice() {
+        let mut test = Array2d::from_fn(3, 3, |pos| pos.y * 3 + pos.x);
+        assert_eq!(test.row_slice(1), &[3, 4, 5]);
+
+        test.row_slice_mut(2).copy_from_slice(&[0, 0, 0]);
+        assert_eq!(test.as_slice(), &[0, 1, 2, 3, 4, 5, 0, 0, 0]);
+
+        let padded : Array2d<i32> = Array2d::new_with_stride(2, 2, 4);
+        assert_eq!(padded.row_slice(1).len(), 2);
+    }
+
+    #[test]
+    fn test_fill_row_and_column() {
+        let mut test : Array2d<i32> = Array2d::new(4, 4);
+        test.fill_row(1, 1);
+        for (pos, value) in &test {
+            assert_eq!(*value, (pos.y == 1) as i32);
+        }
+
+        let mut test : Array2d<i32> = Array2d::new(4, 4);
+        test.fill_column(3, 1);
+        for (pos, value) in &test {
+            assert_eq!(*value, (pos.x == 3) as i32);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fill_column_out_of_range() {
+        let mut test : Array2d<i32> = Array2d::new(4, 4);
+        test.fill_column(4, 1);
+    }
+
+    #[test]
+    fn test_replace_all() {
+        let mut test = Array2d::from_vec(3, 2, vec![1, 2, 1, 3, 1, 2]).unwrap();
+        assert_eq!(test.replace_all(&1, 5), 3);
+        assert_eq!(test.as_slice(), &[5, 2, 5, 3, 5, 2]);
+        assert_eq!(test.replace_all(&1, 5), 0);
+    }
+
+    #[test]
+    fn test_iter_non_default() {
+        let mut test : Array2d<i32> = Array2d::new(4, 4);
+        test.set(IVec2::new(1, 2), 5);
+        test.set(IVec2::new(3, 0), 7);
+
+        let cells : Vec<(IVec2, &i32)> = test.iter_non_default().collect();
+        assert_eq!(cells, vec![(IVec2::new(3, 0), &7), (IVec2::new(1, 2), &5)]);
+    }
+
+    #[test]
+    fn test_centered() {
+        let test = Array2d::from_fn(5, 5, |pos| pos);
+        assert_eq!(test.center(), IVec2::new(2, 2));
+        assert_eq!(*test.get_centered(IVec2::ZERO), IVec2::new(2, 2));
+        assert_eq!(*test.get_centered(IVec2::new(-2, 1)), IVec2::new(0, 3));
+
+        let even : Array2d<i32> = Array2d::new(4, 2);
+        assert_eq!(even.center(), IVec2::new(2, 1));
+    }
+
+    #[test]
+    fn test_reductions() {
+        let test = Array2d::from_vec(3, 2, vec![4, -2, 7, 0, 3, 1]).unwrap();
+        assert_eq!(test.min_value(), Some(&-2));
+        assert_eq!(test.max_value(), Some(&7));
+        assert_eq!(test.sum(), 13);
+
+        let empty : Array2d<i32> = Array2d::new(0, 3);
+        assert_eq!(empty.max_value(), None);
+        assert_eq!(empty.sum(), 0);
+    }
+
+    #[test]
+    fn test_argmax_argmin() {
+        let mut test : Array2d<i32> = Array2d::new(4, 3);
+        test.set(IVec2::new(2, 1), 9);
+        test.set(IVec2::new(3, 2), -1);
+        test.set(IVec2::new(0, 2), -1);
+
+        assert_eq!(test.argmax(), Some(IVec2::new(2, 1)));
+        assert_eq!(test.argmin(), Some(IVec2::new(0, 2)));
+
+        let empty : Array2d<i32> = Array2d::new(0, 0);
+        assert_eq!(empty.argmax(), None);
+    }
+
+    #[test]
+    fn test_push_row() {
+        let mut test = Array2d::from_fn(3, 2, |pos| pos.y * 3 + pos.x);
+        test.push_row(&[6, 7, 8]);
+
+        assert_eq!(test.dimensions(), IVec2::new(3, 3));
+        for (pos, value) in &test {
+            assert_eq!(*value, pos.y * 3 + pos.x);
+        }
+
+        let mut padded : Array2d<i32> = Array2d::new_with_stride(2, 1, 4);
+        padded.push_row(&[1, 2]);
+        assert_eq!(padded.as_slice().len(), 8);
+        assert_eq!(padded.row_slice(1), &[1, 2]);
+
+        let mut empty : Array2d<i32> = Array2d::new(0, 0);
+        empty.push_row(&[1, 2]);
+        assert_eq!(empty.dimensions(), IVec2::new(2, 1));
+    }
+
+    #[test]
+    fn test_push_column() {
+        let mut test = Array2d::from_fn(3, 2, |pos| pos.y * 10 + pos.x);
+        test.push_column(&[3, 13]);
+
+        assert_eq!(test.dimensions(), IVec2::new(4, 2));
+        for (pos, value) in &test {
+            assert_eq!(*value, pos.y * 10 + pos.x);
+        }
+
+        let mut padded : Array2d<i32> = Array2d::new_with_stride(1, 2, 3);
+        padded.push_column(&[1, 2]);
+        assert_eq!(padded.as_slice(), &[0, 1, 0, 2]);
+
+        let mut empty : Array2d<i32> = Array2d::new(0, 0);
+        empty.push_column(&[1, 2, 3]);
+        assert_eq!(empty.dimensions(), IVec2::new(1, 3));
+    }
+
+    #[test]
+    fn test_remove_row() {
+        let mut test = Array2d::from_fn(3, 3, |pos| pos.y * 3 + pos.x);
+        test.remove_row(1);
+
+        assert_eq!(test.dimensions(), IVec2::new(3, 2));
+        assert_eq!(test.as_slice(), &[0, 1, 2, 6, 7, 8]);
+
+        test.remove_row(1);
+        test.remove_row(0);
+        assert!(test.is_empty());
+    }
+
+    #[test]
+    fn test_remove_column() {
+        let mut test = Array2d::from_fn(3, 3, |pos| pos.y * 3 + pos.x);
+        test.remove_column(1);
+
+        assert_eq!(test.dimensions(), IVec2::new(2, 3));
+        assert_eq!(test.as_slice(), &[0, 2, 3, 5, 6, 8]);
+
+        let mut padded : Array2d<i32> = Array2d::new_with_stride(3, 2, 4);
+        padded.apply(|pos, value| *value = pos.y * 3 + pos.x);
+        padded.remove_column(0);
+        assert_eq!(padded.stride(), 3);
+        assert_eq!(padded.into_vec(), vec![1, 2, 4, 5]);
+    }
+
+    #[test]
+    fn test_get_flat() {
+        let mut test : Array2d<i32> = Array2d::new_with_stride(2, 2, 3);
+        *test.get_flat_mut(2).unwrap() = 5;
+
+        assert_eq!(*test.get(IVec2::new(0, 1)), 5);
+        assert_eq!(test.get_flat(2), Some(&5));
+        assert_eq!(test.get_flat(4), None);
+        assert_eq!(test.get_flat_mut(4), None);
+    }
+
+    #[test]
+    fn test_index_of() {
+        let test = Array2d::from_fn(4, 3, |pos| pos);
+        for i in 0..test.len() {
+            let pos = test.position_of(i);
+            assert_eq!(test.index_of(pos), i);
+            assert_eq!(test[i], pos);
+        }
+    }
+
+    #[test]
+    fn test_into_iter_owned() {
+        let test = Array2d::from_vec(2, 2, vec![String::from("a"), "b".into(), "c".into(), "d".into()]).unwrap();
+        let cells : Vec<(IVec2, String)> = test.into_iter().collect();
+
+        assert_eq!(cells, vec![
+            (IVec2::new(0, 0), String::from("a")),
+            (IVec2::new(1, 0), String::from("b")),
+            (IVec2::new(0, 1), String::from("c")),
+            (IVec2::new(1, 1), String::from("d")),
+        ]);
+
+        let mut padded : Array2d<i32> = Array2d::new_with_stride(2, 2, 3);
+        padded.fill(1);
+        assert_eq!(padded.into_iter().len(), 4);
+    }
+
+    #[test]
+    fn test_for_each_mut() {
+        let mut test : Array2d<i32> = Array2d::new_with_stride(3, 2, 4);
+        test.for_each_mut(|pos, value| *value = pos.y * 3 + pos.x);
+
+        assert_eq!(test.into_vec(), (0..6).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_map_to_grid_bounded() {
+        let dims = IVec2::new(4, 2);
+        assert_eq!(map_to_grid_bounded(Vec2::new(70.0, 10.0), 64.0, dims), Some(IVec2::new(1, 0)));
+
+        // Left of the grid, below the grid and right past the last column.
+        assert_eq!(map_to_grid_bounded(Vec2::new(-0.5, 10.0), 64.0, dims), None);
+        assert_eq!(map_to_grid_bounded(Vec2::new(10.0, -10.0), 64.0, dims), None);
+        assert_eq!(map_to_grid_bounded(Vec2::new(256.0, 10.0), 64.0, dims), None);
+    }
+
+    #[test]
+    fn test_fill_circle() {
+        let mut test : Array2d<bool> = Array2d::new(5, 5);
+        test.fill_circle(IVec2::new(2, 2), 1, true);
+
+        let cells : Vec<IVec2> = test.iter_non_default().map(|(pos, _)| pos).collect();
+        assert_eq!(cells, vec![
+            IVec2::new(2, 1),
+            IVec2::new(1, 2),
+            IVec2::new(2, 2),
+            IVec2::new(3, 2),
+            IVec2::new(2, 3),
+        ]);
+
+        let mut test : Array2d<bool> = Array2d::new(5, 5);
+        test.fill_circle(IVec2::new(0, 0), 2, true);
+        assert_eq!(test.count_where(|value| *value), 6);
+    }
+
+    #[test]
+    fn test_grid_cell_center() {
+        assert_eq!(grid_cell_center(IVec2::new(1, 1), 64.0), Vec2::new(96.0, 96.0));
+        assert_eq!(grid_cell_center(IVec2::new(-1, 0), 64.0), Vec2::new(-32.0, 32.0));
+
+        let center = grid_cell_center(IVec2::new(3, 2), 16.0);
+        assert_eq!(map_to_grid_with_offset(center, 16.0), (IVec2::new(3, 2), Vec2::splat(8.0)));
+    }
+
+    // The side length is chosen relative to the pointer width, so the number
+    // of cells overflows on 32 bit targets as well as on 64 bit targets.
+    #[test]
+    #[should_panic(expected = "overflow")]
+    fn test_new_overflow() {
+        let side = 1 << (usize::BITS / 2);
+        let _ : Array2d<u8> = Array2d::new(side, side);
+    }
+
+    #[test]
+    fn test_try_get_1d_from_2d() {
+        let side = 1 << (usize::BITS / 2);
+        assert_eq!(try_get_1d_from_2d(side, 1, side - 1), Some(side * (side - 1) + 1));
+        assert_eq!(try_get_1d_from_2d(side, 0, side), None);
+    }
+
+    #[test]
+    fn test_new_uninit_filled() {
+        let test = Array2d::new_uninit_filled(3, 2, -7);
+        assert_eq!(test.dimensions(), IVec2::new(3, 2));
+        assert!(test.iter().all(|(_, value)| *value == -7));
+    }
+
+    #[test]
+    fn test_without_default() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Tile(u8);
+
+        let mut test = Array2d::from_vec(2, 2, vec![Tile(1), Tile(2), Tile(3), Tile(4)]).unwrap();
+        test.set(IVec2::new(0, 1), Tile(9));
+
         assert_eq!(test.len(), 4);
-        test.resize(3, 3);
-        assert_eq!(test.len(), 9);
+        assert_eq!(*test.get(IVec2::new(1, 0)), Tile(2));
+        assert_eq!(test[2], Tile(9));
+        assert_eq!(test.iter().count(), 4);
+        assert_eq!(test.clone(), test);
     }
 
     #[test]
-    fn test_getter_and_setter() {
-        let mut test : Array2d<usize> = Array2d::new(4, 4);
-        assert_eq!(test.len(), 16);
+    fn test_map_in_place() {
+        let mut test = Array2d::from_fn(3, 2, |pos| pos.y * 3 + pos.x);
+        test.map_in_place(|value| value * 2);
 
-        let mut pos = IVec2{ x : 0, y : 0};
-        assert_eq!(*test.get(pos), 0);
-        test.set(pos, 1);
-        assert_eq!(*test.get(pos), 1);
+        assert_eq!(test.dimensions(), IVec2::new(3, 2));
+        assert_eq!(test.as_slice(), &[0, 2, 4, 6, 8, 10]);
+    }
 
-        pos = IVec2{ x : 3, y : 3};
-        assert_eq!(*test.get(pos), 0);
-        test.set(pos, 64);
-        assert_eq!(*test.get(pos), 64);
+    #[test]
+    fn test_copy_from() {
+        let data : Vec<i32> = (0..16).collect();
+
+        let mut test : Array2d<i32> = Array2d::new(4, 4);
+        test.copy_from(&data);
+        assert_eq!(*test.get(IVec2::new(0, 0)), 0);
+        assert_eq!(*test.get(IVec2::new(3, 1)), 7);
+        assert_eq!(*test.get(IVec2::new(2, 3)), 14);
+
+        let mut padded : Array2d<i32> = Array2d::new_with_stride(4, 4, 6);
+        padded.copy_from(&data);
+        assert_eq!(padded, test);
     }
 
     #[test]
-    fn test_quantize_element() {
-        let data = get_quantize_data();
-        for (x, y, size, x1, y1) in data {
-            let vec = Vec2{ x, y };
-            let r = quantize_to_grid(vec, size);
+    #[should_panic]
+    fn test_copy_from_invalid_length() {
+        let mut test : Array2d<i32> = Array2d::new(4, 4);
+        test.copy_from(&[1, 2, 3]);
+    }
 
-            assert_eq!(r.x, x1);
-            assert_eq!(r.y, y1);
+    #[test]
+    fn test_array_error_variants() {
+        let r = Array2d::from_iter_flat(2, 2, 0..3);
+        assert!(matches!(r, Err(ArrayError::SizeMismatch { expected: 4, got: 3 })));
+
+        let mut test : Array2d<i32> = Array2d::new(2, 2);
+        let r = test.set_region(IVec2::new(1, 1), IVec2::new(2, 1), &[1, 2]);
+        assert!(matches!(r, Err(ArrayError::OutOfBounds { pos, .. }) if pos == IVec2::new(2, 1)));
+
+        let e = ArrayError::SizeMismatch::<IVec2> { expected: 4, got: 3 };
+        assert_eq!(e.to_string(), "expected 4 items but got 3");
+    }
+
+    #[test]
+    fn test_resize_with_value() {
+        let mut test : Array2d<i32> = Array2d::from_fn(2, 2, |v| v.x + v.y * 2);
+        test.resize_with_value(3, 3, 9);
+
+        assert_eq!(*test.get(IVec2::new(0, 0)), 0);
+        assert_eq!(*test.get(IVec2::new(1, 0)), 1);
+        assert_eq!(*test.get(IVec2::new(0, 1)), 2);
+        assert_eq!(*test.get(IVec2::new(1, 1)), 3);
+        assert_eq!(*test.get(IVec2::new(2, 0)), 9);
+        assert_eq!(*test.get(IVec2::new(2, 2)), 9);
+
+        test.resize_with_value(1, 2, 7);
+        assert_eq!(test.into_vec(), vec![0, 2]);
+    }
+
+    #[test]
+    fn test_new_with_origin() {
+        let mut test : Array2d<i32> = Array2d::new_with_origin(IVec2::new(-2, -2), IVec2::new(4, 4));
+        assert_eq!(test.origin(), IVec2::new(-2, -2));
+        assert_eq!(test.dimensions(), IVec2::new(4, 4));
+
+        test.set(IVec2::new(-2, -2), 1);
+        test.set(IVec2::new(-1, 0), 2);
+        test[IVec2::new(1, 1)] = 3;
+        assert_eq!(*test.get(IVec2::new(-2, -2)), 1);
+        assert_eq!(*test.get(IVec2::new(-1, 0)), 2);
+        assert_eq!(test.as_slice()[0], 1);
+        assert_eq!(test.as_slice()[9], 2);
+        assert_eq!(test.as_slice()[15], 3);
+
+        assert!(test.contains_point(IVec2::new(-2, 1)));
+        assert!(!test.contains_point(IVec2::new(2, 0)));
+        assert!(!test.contains_point(IVec2::new(0, -3)));
+        assert_eq!(test.get_checked(IVec2::new(2, 2)), None);
+        assert_eq!(test.get_checked(IVec2::new(i32::MAX, 0)), None);
+        assert_eq!(
+            test.try_set(IVec2::new(-3, 0), 4),
+            Err(ArrayError::OutOfBounds { pos: IVec2::new(-3, 0), dims: IVec2::new(4, 4) })
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_with_origin_invalid_position() {
+        let test : Array2d<i32> = Array2d::new_with_origin(IVec2::new(-2, -2), IVec2::new(4, 4));
+        test.get(IVec2::new(2, 2));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_origin() {
+        let test : Array2d<i32> = Array2d::new_with_origin(IVec2::new(-2, 3), IVec2::new(2, 2));
+
+        let json = serde_json::to_string(&test).unwrap();
+        let back : Array2d<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.origin(), IVec2::new(-2, 3));
+        assert_eq!(test, back);
+    }
+
+    #[test]
+    fn test_zip() {
+        let terrain = Array2d::from_fn(3, 2, |pos| pos.x);
+        let mut moisture : Array2d<i32> = Array2d::new_with_stride(3, 2, 4);
+        moisture.fill(10);
+        moisture.set(IVec2::new(2, 1), 20);
+
+        assert!(terrain.dimensions_match(&moisture));
+        let sum = terrain.zip(&moisture, |a, b| a + b).unwrap();
+        assert_eq!(sum.into_vec(), vec![10, 11, 12, 10, 11, 22]);
+    }
+
+    #[test]
+    fn test_zip_dimension_mismatch() {
+        let a : Array2d<i32> = Array2d::new(3, 2);
+        let b : Array2d<f32> = Array2d::new(2, 3);
+
+        assert!(!a.dimensions_match(&b));
+        let r = a.zip(&b, |x, y| *x as f32 + y);
+        assert_eq!(r.err(), Some(ArrayError::DimMismatch { expected: IVec2::new(3, 2), got: IVec2::new(2, 3) }));
+    }
+
+    #[test]
+    fn test_set_region_invalid_data() {
+        let mut array : Array2d<i32> = Array2d::new(4, 4);
+        let before = array.clone();
+
+        let r = array.set_region(IVec2::new(1, 1), IVec2::new(2, 2), &[1, 2, 3]);
+        assert_eq!(r, Err(ArrayError::SizeMismatch { expected: 4, got: 3 }));
+
+        let r = array.set_region(IVec2::new(1, 1), IVec2::new(-1, 2), &[]);
+        assert!(matches!(r, Err(ArrayError::OutOfBounds { .. })));
+        assert_eq!(array, before);
+    }
+
+    #[test]
+    #[should_panic(expected = "Array is empty")]
+    fn test_get_wrapped_empty() {
+        let test : Array2d<i32> = Array2d::new(0, 3);
+        test.get_wrapped(IVec2::new(1, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Array is empty")]
+    fn test_get_clamped_empty() {
+        let test : Array2d<i32> = Array2d::new(3, 0);
+        test.get_clamped(IVec2::new(1, 1));
+    }
+
+    #[test]
+    fn test_rows_without_columns() {
+        let test : Array2d<i32> = Array2d::new(0, 3);
+        assert_eq!(test.rows().count(), 3);
+        assert!(test.rows().all(|mut row| row.next().is_none()));
+    }
+
+    #[test]
+    fn test_push_row_without_default() {
+        #[derive(Clone, Debug, PartialEq)]
+        struct Tile(u8);
+
+        let mut test = Array2d::from_vec(2, 1, vec![Tile(1), Tile(2)]).unwrap();
+        test.push_row(&[Tile(3), Tile(4)]);
+        assert_eq!(*test.get(IVec2::new(1, 1)), Tile(4));
+
+        let mut columnless : Array2d<i32> = Array2d::new_with_stride(0, 1, 2);
+        columnless.push_row(&[]);
+        assert_eq!(columnless.dimensions(), IVec2::new(0, 2));
+        assert!(columnless.as_slice().is_empty());
+    }
+
+    #[test]
+    fn test_origin_round_trip() {
+        let mut test : Array2d<i32> = Array2d::new_with_origin(IVec2::new(-2, -2), IVec2::new(4, 4));
+        test.for_each_mut(|pos, value| *value = pos.x * 10 + pos.y);
+
+        let positions : Vec<IVec2> = test.iter().map(|(pos, _)| pos).collect();
+        assert_eq!(positions.first(), Some(&IVec2::new(-2, -2)));
+        assert_eq!(positions.last(), Some(&IVec2::new(1, 1)));
+        assert_eq!(test.positions().collect::<Vec<_>>(), positions);
+
+        for (pos, value) in &test {
+            assert_eq!(test.get(pos), value);
+            assert!(test.contains_point(pos));
+            assert_eq!(test.position_of(test.index_of(pos)), pos);
+        }
+
+        for (pos, value) in test.iter_mut() {
+            *value = pos.x * 10 + pos.y;
+        }
+
+        assert_eq!(test.find_position(|value| *value == -11), Some(IVec2::new(-1, -1)));
+        assert_eq!(test.get(test.argmax().unwrap()), &11);
+        assert_eq!(test.argmin(), Some(IVec2::new(-2, -2)));
+        assert_eq!(test.center(), IVec2::ZERO);
+        assert_eq!(*test.get_centered(IVec2::ZERO), 0);
+        assert_eq!(*test.get_wrapped(IVec2::new(-3, -3)), 11);
+        assert_eq!(*test.get_clamped(IVec2::new(-5, 5)), -19);
+        assert_eq!(test.row(-1).copied().collect::<Vec<_>>(), vec![-21, -11, -1, 9]);
+        assert_eq!(test.column(1).copied().collect::<Vec<_>>(), vec![8, 9, 10, 11]);
+
+        for (pos, _) in test.neighbors4(IVec2::new(-2, 1)) {
+            assert!(test.contains_point(pos));
         }
+        assert_eq!(test.neighbors8(IVec2::new(-2, -2)).count(), 3);
+
+        let owned : Vec<IVec2> = test.clone().into_iter().map(|(pos, _)| pos).collect();
+        assert_eq!(owned, positions);
     }
 
     #[test]
-    fn test_map_element() {
-        let data = get_mapping_data();
-        for (x, y, size, x1, y1) in data {
-            let vec = Vec2{ x, y };
-            let r = map_to_grid_vec2(vec, size);
+    fn test_origin_editing() {
+        let mut test : Array2d<i32> = Array2d::new_with_origin(IVec2::new(-2, -2), IVec2::new(4, 4));
 
-            assert_eq!(r.x, x1 as i32);
-            assert_eq!(r.y, y1 as i32);
+        test.fill_rect(IVec2::new(-3, -3), IVec2::new(2, 2), 1);
+        assert_eq!(*test.get(IVec2::new(-2, -2)), 1);
+        assert_eq!(test.count_where(|value| *value == 1), 1);
+
+        test.draw_line(IVec2::new(-2, 1), IVec2::new(1, 1), 2);
+        assert_eq!(test.row(1).copied().collect::<Vec<_>>(), vec![2, 2, 2, 2]);
+
+        test.flood_fill(IVec2::new(0, 0), 3);
+        assert_eq!(test.count_where(|value| *value == 3), 11);
+
+        test.fill_circle(IVec2::new(0, 0), 0, 4);
+        assert_eq!(*test.get(IVec2::ZERO), 4);
+
+        let r = test.set_region(IVec2::new(1, 1), IVec2::new(2, 1), &[5, 6]);
+        assert_eq!(r, Err(ArrayError::OutOfBounds { pos: IVec2::new(2, 1), dims: IVec2::new(4, 4) }));
+        test.set_region(IVec2::new(0, -2), IVec2::new(2, 1), &[5, 6]).unwrap();
+        assert_eq!(*test.get(IVec2::new(1, -2)), 6);
+
+        let mut stamp : Array2d<i32> = Array2d::new_with_origin(IVec2::new(7, 7), IVec2::new(1, 1));
+        stamp.fill(9);
+        test.blit(IVec2::new(-1, 0), &stamp);
+        assert_eq!(*test.get(IVec2::new(-1, 0)), 9);
+    }
+
+    #[test]
+    fn test_origin_derived_arrays() {
+        let test = Array2d::from_fn(4, 4, |pos| pos).with_origin(IVec2::new(-2, -2));
+        assert_eq!(test.origin(), IVec2::new(-2, -2));
+        assert_eq!(*test.get(IVec2::new(-2, -1)), IVec2::new(0, 1));
+
+        // Derived arrays are fresh arrays starting at zero.
+        let region = test.sub_region(IVec2::new(-1, 0), IVec2::new(2, 2)).unwrap();
+        assert_eq!(region.origin(), IVec2::ZERO);
+        assert_eq!(*region.get(IVec2::new(0, 1)), IVec2::new(1, 3));
+        assert_eq!(test.map(|pos, _| pos).origin(), IVec2::ZERO);
+        assert_eq!(test.transpose().origin(), IVec2::ZERO);
+        assert_eq!(test.rotate_cw().origin(), IVec2::ZERO);
+        assert_eq!(test.rotate_ccw().origin(), IVec2::ZERO);
+        assert_eq!(test.step_cellular(|value, _| *value), test.clone().with_origin(IVec2::ZERO));
+        assert_eq!(test.clone().origin(), test.origin());
+
+        let (min, chunk) = test.chunks(IVec2::new(2, 2)).last().unwrap();
+        assert_eq!(min, IVec2::new(0, 0));
+        assert_eq!(*chunk.get(IVec2::ZERO), IVec2::new(2, 2));
+
+        let (corner, cells) = test.windows(IVec2::new(2, 2)).next().unwrap();
+        assert_eq!(corner, IVec2::new(-2, -2));
+        assert_eq!(*cells[0], IVec2::ZERO);
+
+        let path = test.find_path(IVec2::new(-2, -2), IVec2::new(-2, 0), |_| true).unwrap();
+        assert_eq!(path, vec![IVec2::new(-2, -2), IVec2::new(-2, -1), IVec2::new(-2, 0)]);
+
+        let field = test.distance_field(|pos| *pos == IVec2::ZERO);
+        assert_eq!(*field.get(IVec2::new(2, 0)), 2.0);
+
+        let heights = test.map(|pos, _| pos.x as f32).with_origin(test.origin());
+        assert_eq!(heights.sample_bilinear(Vec2::new(-0.5, 0.5)), -1.0);
+        let identity = [[0.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 0.0]];
+        assert_eq!(heights.convolve3x3(identity), heights.clone().with_origin(IVec2::ZERO));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_iter_mut_origin() {
+        use rayon::prelude::*;
+
+        let mut test : Array2d<IVec2> = Array2d::new_with_origin(IVec2::new(-2, -2), IVec2::new(4, 4));
+        test.par_iter_mut().for_each(|(pos, value)| *value = pos);
+
+        for (pos, value) in &test {
+            assert_eq!(*value, pos);
         }
     }
+
+    #[test]
+    fn test_from_vec_overflow() {
+        let side = 1 << (usize::BITS / 2);
+        let r : Result<Array2d<u8>, _> = Array2d::from_vec(side, side, Vec::new());
+        assert_eq!(r, Err(ArrayError::DimensionOverflow));
+        let r : Result<Array2d<u8>, _> = Array2d::from_iter_flat(side, side, std::iter::empty());
+        assert_eq!(r, Err(ArrayError::DimensionOverflow));
+    }
 }
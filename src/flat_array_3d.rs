@@ -1,6 +1,10 @@
 use std::ops::{Index, IndexMut};
 use bevy::prelude::*;
 
+use crate::dimensions::{checked_len, try_len};
+use crate::error::ArrayError;
+use crate::flat_array_2d::{get_2d_from_1d_ivec2, Array2d};
+
 /// Get the array index from a 3 point. This is the inverse operation to
 /// get_3d_from_1d.
 /// 
@@ -23,6 +27,22 @@ pub fn get_1d_from_3d(max_x: usize, max_y: usize, x: usize, y: usize, z: usize)
     (z * max_x * max_y) + (y * max_x) + x
 }
 
+/// Get the array index for the given position like get_1d_from_3d, but
+/// returns None instead of overflowing on huge dimensions.
+///
+/// # Example
+/// ```
+/// use bevy_flat_arrays::prelude::tools::try_get_1d_from_3d;
+/// assert_eq!(try_get_1d_from_3d(2, 2, 1, 0, 1), Some(5));
+/// assert_eq!(try_get_1d_from_3d(usize::MAX, 2, 0, 0, 1), None);
+/// ```
+pub fn try_get_1d_from_3d(max_x: usize, max_y: usize, x: usize, y: usize, z: usize) -> Option<usize> {
+    z.checked_mul(max_x)?
+        .checked_mul(max_y)?
+        .checked_add(y.checked_mul(max_x)?)?
+        .checked_add(x)
+}
+
 /// Get the position from the array index. This is the inverse operation to
 /// get_1d_from_3d.
 /// 
@@ -91,6 +111,62 @@ pub fn map_to_grid_vec3(v : Vec3, grid_size : f32) -> IVec3 {
     IVec3 { x, y, z, }
 }
 
+/// Map a world vector to a cell on a grid with box shaped cells. Each axis
+/// is divided by its own cell size and the cell is returned as an index.
+/// Panics if a component of `cell` is zero.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_flat_arrays::prelude::tools::map_to_grid_vec3_nonuniform;
+/// let cell = Vec3 { x : 64.0, y : 32.0, z : 16.0 };
+/// let mapped = map_to_grid_vec3_nonuniform(Vec3 { x : 70.0, y : 70.0, z : 70.0 }, cell);
+/// assert_eq!(IVec3 { x : 1, y : 2, z : 4 }, mapped);
+/// ```
+pub fn map_to_grid_vec3_nonuniform(v : Vec3, cell : Vec3) -> IVec3 {
+    assert!(cell.x != 0.0 && cell.y != 0.0 && cell.z != 0.0, "Invalid cell size");
+
+    (v / cell).floor().as_ivec3()
+}
+
+/// Map a world vector to a cell on a predefined grid and return the offset
+/// of the vector inside of that cell as well. Unlike map_to_grid_vec3 the
+/// cell is returned as an index, not in world units. Each component of the
+/// offset lies in `[0, grid_size)`.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_flat_arrays::prelude::tools::map_to_grid_with_offset_vec3;
+/// let v = Vec3 { x : 70.0, y : 10.0, z : 130.0 };
+/// let (cell, offset) = map_to_grid_with_offset_vec3(v, 64.0);
+/// assert_eq!(IVec3 { x : 1, y : 0, z : 2 }, cell);
+/// assert_eq!(Vec3 { x : 6.0, y : 10.0, z : 2.0 }, offset);
+/// ```
+pub fn map_to_grid_with_offset_vec3(v : Vec3, grid_size : f32) -> (IVec3, Vec3) {
+    let cell = (v / grid_size).floor();
+    let offset = v - cell * grid_size;
+
+    (cell.as_ivec3(), offset)
+}
+
+/// Returns the center of a cell in world units, e.g. to snap an object to the
+/// middle of a voxel.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_flat_arrays::prelude::tools::grid_cell_center_vec3;
+/// let center = grid_cell_center_vec3(IVec3 { x : 1, y : 0, z : 2 }, 64.0);
+/// assert_eq!(Vec3 { x : 96.0, y : 32.0, z : 160.0 }, center);
+/// ```
+pub fn grid_cell_center_vec3(cell : IVec3, grid_size : f32) -> Vec3 {
+    (cell.as_vec3() + Vec3::splat(0.5)) * grid_size
+}
+
+/// Offsets of the face neighbors of a cell.
+const NEIGHBORS_6: [IVec3; 6] = [
+    IVec3::new(0, 0, -1),
+    IVec3::new(0, -1, 0),
+    IVec3::new(-1, 0, 0),
+    IVec3::new(1, 0, 0),
+    IVec3::new(0, 1, 0),
+    IVec3::new(0, 0, 1),
+];
 
 /// # Array3d
 /// 
@@ -108,24 +184,31 @@ pub fn map_to_grid_vec3(v : Vec3, grid_size : f32) -> IVec3 {
 /// no additional comnputation takes place.
 /// 
 /// The memory for the array is allocated when a new array is created and can be resized
-/// using the resize function. To make it easier to allocate memory, all types are required
-/// to implement the Default trait. 
-pub struct Array3d<T: std::default::Default> {
+/// using the resize function. Only the functions that have to create new cells, like `new`
+/// and `resize`, require the Default trait. An array of any other type can be built with
+/// `from_vec` or `from_fn`.
+/// 
+/// # Bevy
+/// 
+/// The array is a component, so it can be stored directly on an entity, e.g.
+/// `commands.spawn(Array3d::<Tile>::new(16, 16, 16))`. This requires `T` to be
+/// `Send + Sync + 'static`.
+#[derive(Component, Reflect)]
+pub struct Array3d<T> {
     width: usize,
     height: usize,
     depth: usize,
     array: Vec<T>,
 }
 
-impl<T: std::default::Default> Array3d<T> {
+impl<T> Array3d<T> {
     /// Constructs a new array.
-    pub fn new(width: usize, height: usize, depth: usize) -> Self {
-        assert!(width > 0);
-        assert!(height > 0);
-        assert!(depth > 0);
-
+    pub fn new(width: usize, height: usize, depth: usize) -> Self
+    where
+        T: std::default::Default,
+    {
         let mut r: Vec<T> = Vec::new();
-        r.resize_with(width * height * depth, || T::default());
+        r.resize_with(checked_len(&[width, height, depth]), || T::default());
 
         Array3d {
             width,
@@ -135,12 +218,153 @@ impl<T: std::default::Default> Array3d<T> {
         }
     }
 
+    /// Constructs a new array that takes ownership of the given buffer. The
+    /// buffer is expected to be laid out slice by slice and has to hold exactly
+    /// width * height * depth items.
+    pub fn from_vec(width: usize, height: usize, depth: usize, data: Vec<T>) -> Result<Self, ArrayError<IVec3>> {
+        let Some(expected) = try_len(&[width, height, depth]) else {
+            return Err(ArrayError::DimensionOverflow);
+        };
+        if data.len() != expected {
+            return Err(ArrayError::SizeMismatch { expected, got: data.len() });
+        }
+
+        Ok(Array3d {
+            width,
+            height,
+            depth,
+            array: data,
+        })
+    }
+
+    /// Constructs a new array from the items of `iter` in flat order. The
+    /// iterator has to yield exactly width * height * depth items. An
+    /// iterator that yields more is not drained any further, so unbounded
+    /// iterators are rejected right away.
+    pub fn from_iter_flat(width: usize, height: usize, depth: usize, iter: impl IntoIterator<Item = T>) -> Result<Self, ArrayError<IVec3>> {
+        let Some(expected) = try_len(&[width, height, depth]) else {
+            return Err(ArrayError::DimensionOverflow);
+        };
+        let mut iter = iter.into_iter();
+        let data : Vec<T> = iter.by_ref().take(expected).collect();
+
+        if iter.next().is_some() {
+            return Err(ArrayError::TooManyItems { expected });
+        }
+
+        Array3d::from_vec(width, height, depth, data)
+    }
+
+    /// Constructs a new array and initializes every cell by calling `f` with
+    /// the position of the cell. The cells are generated in flat order.
+    pub fn from_fn(width: usize, height: usize, depth: usize, mut f: impl FnMut(IVec3) -> T) -> Self {
+        let array = (0..checked_len(&[width, height, depth]))
+            .map(|i| f(get_3d_from_1d_ivec3(width, height, i)))
+            .collect();
+
+        Array3d {
+            width,
+            height,
+            depth,
+            array,
+        }
+    }
+
+    /// Constructs a new array by stacking the given layers along the z axis.
+    /// All layers have to share the same width and height. Returns
+    /// `ArrayError::ZeroDimension` if no layer is given.
+    pub fn from_layers(layers: Vec<Array2d<T>>) -> Result<Self, ArrayError<IVec2>> {
+        if layers.is_empty() {
+            return Err(ArrayError::ZeroDimension);
+        }
+
+        let dims = layers[0].dimensions();
+        let depth = layers.len();
+        let mut array = Vec::with_capacity(layers[0].len() * depth);
+
+        for layer in layers {
+            if layer.dimensions() != dims {
+                return Err(ArrayError::DimMismatch { expected: dims, got: layer.dimensions() });
+            }
+
+            array.extend(layer.into_vec());
+        }
+
+        Ok(Array3d {
+            width: dims.x as usize,
+            height: dims.y as usize,
+            depth,
+            array,
+        })
+    }
+
     /// Resize this array to the given dimensions.
-    pub fn resize(&mut self, width : usize, heigth : usize, depth : usize) {
+    pub fn resize(&mut self, width : usize, heigth : usize, depth : usize)
+    where
+        T: std::default::Default,
+    {
         self.height = heigth;
         self.width = width;
         self.depth = depth;
-        self.array.resize_with(width * heigth * depth, || T::default());
+        self.array.resize_with(checked_len(&[width, heigth, depth]), || T::default());
+    }
+
+    /// Resize this array to the given dimensions while keeping every cell at
+    /// its position. Cells outside of the new bounds are dropped and new cells
+    /// are set to their default value.
+    pub fn resize_preserving(&mut self, width : usize, height : usize, depth : usize)
+    where
+        T: std::default::Default,
+    {
+        let mut array : Vec<T> = Vec::new();
+        array.resize_with(checked_len(&[width, height, depth]), || T::default());
+
+        let old = std::mem::replace(&mut self.array, array);
+        for (i, value) in old.into_iter().enumerate() {
+            let (x, y, z) = get_3d_from_1d(self.width, self.height, i);
+            if x < width && y < height && z < depth {
+                self.array[get_1d_from_3d(width, height, x, y, z)] = value;
+            }
+        }
+
+        self.width = width;
+        self.height = height;
+        self.depth = depth;
+    }
+
+    /// Resize this array to the given dimensions while keeping every cell at
+    /// its position, like `resize_preserving`, but set new cells to `value`
+    /// instead of their default value.
+    pub fn resize_with_value(&mut self, width : usize, height : usize, depth : usize, value : T)
+    where
+        T: Clone,
+    {
+        let array = vec![value; checked_len(&[width, height, depth])];
+
+        let old = std::mem::replace(&mut self.array, array);
+        for (i, value) in old.into_iter().enumerate() {
+            let (x, y, z) = get_3d_from_1d(self.width, self.height, i);
+            if x < width && y < height && z < depth {
+                self.array[get_1d_from_3d(width, height, x, y, z)] = value;
+            }
+        }
+
+        self.width = width;
+        self.height = height;
+        self.depth = depth;
+    }
+
+    /// Release any memory the backing storage holds beyond the cells of this
+    /// array, e.g. after resizing it to smaller dimensions.
+    pub fn shrink_to_fit(&mut self) {
+        self.array.shrink_to_fit();
+    }
+
+    /// Reserve memory for at least `additional_cells` more cells, so later
+    /// calls to `resize` do not have to reallocate. This only affects the
+    /// capacity of the backing storage, the dimensions stay the same.
+    pub fn reserve(&mut self, additional_cells : usize) {
+        self.array.reserve(additional_cells);
     }
 
     /// Returns the number of items inside this array holds.
@@ -148,32 +372,320 @@ impl<T: std::default::Default> Array3d<T> {
         self.width * self.height * self.depth
     }
 
-    /// Implemented to silence the compiler. Always return false.
+    /// Returns the extent of this array along the x axis.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the extent of this array along the y axis.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the extent of this array along the z axis.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the width, height and depth of this array.
+    pub fn dimensions(&self) -> IVec3 {
+        IVec3::new(self.width as i32, self.height as i32, self.depth as i32)
+    }
+
+    /// Returns the position of the middle cell, i.e.
+    /// `(width / 2, height / 2, depth / 2)`. For an even dimension the cell
+    /// right after the middle is chosen.
+    pub fn center(&self) -> IVec3 {
+        self.dimensions() / 2
+    }
+
+    /// Returns true if this array holds no cells because one of its
+    /// dimensions is zero.
     pub fn is_empty(&self) -> bool {
-        false
+        self.len() == 0
+    }
+
+    /// Returns the backing storage. The cells are laid out slice by slice and
+    /// row by row with x running fastest, so the cell at `(x, y, z)` lives at
+    /// `z * width * height + y * width + x`.
+    pub fn as_slice(&self) -> &[T] {
+        &self.array
+    }
+
+    /// Returns the backing storage mutably. See `as_slice` for the layout.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.array
+    }
+
+    /// Consumes this array and returns the backing storage. See `as_slice`
+    /// for the layout.
+    pub fn into_vec(self) -> Vec<T> {
+        self.array
     }
 
     /// Get the value for the given position.
     pub fn get(&self, v : IVec3) -> &T {
-        let i = get_1d_from_3d_ivec3(self.width, self.height, v);
-        assert!(i < self.len(), "Invalid index");
+        let i = self.checked_index(v).expect("Invalid index");
         &self.array[i]
     }
 
     /// Get a mutable reference for the given position.
     pub fn get_mut(&mut self, v : IVec3) -> &mut T {
-        let i = get_1d_from_3d_ivec3(self.width, self.height, v);
-        assert!(i < self.len(), "Invalid index");
+        let i = self.checked_index(v).expect("Invalid index");
         &mut self.array[i]
     }
 
     /// Update the value for the given position.
     pub fn set(&mut self, v : IVec3, value : T) {
-        let i = get_1d_from_3d_ivec3(self.width, self.height, v);
-        assert!(i < self.len(), "Invalid index");
+        let i = self.checked_index(v).expect("Invalid index");
         self.array[i] = value;
     }
 
+    /// Update the value for the given position. Returns an error instead of
+    /// panicking if the position lies outside of this array.
+    pub fn try_set(&mut self, v : IVec3, value : T) -> Result<(), ArrayError<IVec3>> {
+        let Some(i) = self.checked_index(v) else {
+            return Err(ArrayError::OutOfBounds { pos: v, dims: self.dimensions() });
+        };
+
+        self.array[i] = value;
+        Ok(())
+    }
+
+    /// Exchange the values of the two given positions.
+    pub fn swap(&mut self, a : IVec3, b : IVec3) {
+        let i = self.checked_index(a).expect("Invalid index");
+        let j = self.checked_index(b).expect("Invalid index");
+        self.array.swap(i, j);
+    }
+
+    /// Get the value for the given position. Returns None if the position
+    /// lies outside of this array.
+    pub fn get_checked(&self, v : IVec3) -> Option<&T> {
+        self.checked_index(v).map(|i| &self.array[i])
+    }
+
+    /// Get a mutable reference for the given position. Returns None if the
+    /// position lies outside of this array.
+    pub fn get_mut_checked(&mut self, v : IVec3) -> Option<&mut T> {
+        self.checked_index(v).map(|i| &mut self.array[i])
+    }
+
+    /// Get the value for the given flat index. Returns None if the index is
+    /// not smaller than `len`.
+    pub fn get_flat(&self, i : usize) -> Option<&T> {
+        self.array.get(i)
+    }
+
+    /// Get a mutable reference for the given flat index. Returns None if the
+    /// index is not smaller than `len`.
+    pub fn get_flat_mut(&mut self, i : usize) -> Option<&mut T> {
+        self.array.get_mut(i)
+    }
+
+    /// Get mutable references to several positions at once. Returns None if
+    /// any position lies outside of this array or two positions are equal.
+    pub fn get_many_mut<const N: usize>(&mut self, positions : [IVec3; N]) -> Option<[&mut T; N]> {
+        let mut indices = [0; N];
+        for (index, v) in indices.iter_mut().zip(positions) {
+            *index = self.checked_index(v)?;
+        }
+
+        self.array.get_disjoint_mut(indices).ok()
+    }
+
+    /// Get the value for the given position, wrapping each coordinate around
+    /// the dimensions of this array. Negative coordinates count from the end,
+    /// so `(-1, -1, -1)` is the cell at `(width - 1, height - 1, depth - 1)`.
+    ///
+    /// # Panics
+    /// Panics if this array is empty.
+    pub fn get_wrapped(&self, v : IVec3) -> &T {
+        self.get(self.wrap(v))
+    }
+
+    /// Get a mutable reference for the given position, wrapping each
+    /// coordinate around the dimensions of this array.
+    ///
+    /// # Panics
+    /// Panics if this array is empty.
+    pub fn get_wrapped_mut(&mut self, v : IVec3) -> &mut T {
+        self.get_mut(self.wrap(v))
+    }
+
+    /// Update the value for the given position, wrapping each coordinate
+    /// around the dimensions of this array.
+    ///
+    /// # Panics
+    /// Panics if this array is empty.
+    pub fn set_wrapped(&mut self, v : IVec3, value : T) {
+        self.set(self.wrap(v), value);
+    }
+
+    /// Map any position onto this array using the euclidean remainder.
+    fn wrap(&self, v : IVec3) -> IVec3 {
+        assert!(!self.is_empty(), "Array is empty");
+
+        IVec3 {
+            x: v.x.rem_euclid(self.width as i32),
+            y: v.y.rem_euclid(self.height as i32),
+            z: v.z.rem_euclid(self.depth as i32),
+        }
+    }
+
+    /// Get the value for the given position, clamping each coordinate to the
+    /// nearest edge of this array.
+    ///
+    /// # Panics
+    /// Panics if this array is empty.
+    pub fn get_clamped(&self, v : IVec3) -> &T {
+        assert!(!self.is_empty(), "Array is empty");
+
+        let max = IVec3::new(self.width as i32 - 1, self.height as i32 - 1, self.depth as i32 - 1);
+        self.get(v.clamp(IVec3::ZERO, max))
+    }
+
+    /// Get the value at the given offset from the center of this array, so
+    /// an offset of `(0, 0, 0)` returns the middle cell. See `center`.
+    pub fn get_centered(&self, offset : IVec3) -> &T {
+        self.get(self.center() + offset)
+    }
+
+    /// Returns the array index for the given position or None if any
+    /// coordinate is negative or exceeds the dimensions of this array.
+    fn checked_index(&self, v : IVec3) -> Option<usize> {
+        if !self.contains_point(v) {
+            return None;
+        }
+
+        Some(get_1d_from_3d_ivec3(self.width, self.height, v))
+    }
+
+    /// Returns true if the given position lies inside of this array.
+    pub fn contains_point(&self, v : IVec3) -> bool {
+        v.x >= 0
+            && v.y >= 0
+            && v.z >= 0
+            && (v.x as usize) < self.width
+            && (v.y as usize) < self.height
+            && (v.z as usize) < self.depth
+    }
+
+    /// Returns the flat index of the given position as used by `Index<usize>`
+    /// and `get_flat`. The position is not checked against the bounds of this
+    /// array.
+    pub fn index_of(&self, v : IVec3) -> usize {
+        get_1d_from_3d_ivec3(self.width, self.height, v)
+    }
+
+    /// Returns the position of the given flat index. Inverse of `index_of`.
+    pub fn position_of(&self, i : usize) -> IVec3 {
+        get_3d_from_1d_ivec3(self.width, self.height, i)
+    }
+
+    /// Creates an iterator over the face neighbors of the given position.
+    /// Neighbors outside of this array are skipped.
+    pub fn neighbors6(&self, v : IVec3) -> impl Iterator<Item = (IVec3, &T)> {
+        NEIGHBORS_6.iter().filter_map(move |offset| {
+            let n = v + *offset;
+            self.get_checked(n).map(|value| (n, value))
+        })
+    }
+
+    /// Creates an iterator over all cells of the 3x3x3 block around the given
+    /// position, excluding the position itself. Neighbors outside of this
+    /// array are skipped.
+    pub fn neighbors26(&self, v : IVec3) -> impl Iterator<Item = (IVec3, &T)> {
+        (0..27)
+            .map(|i| IVec3::new(i % 3 - 1, (i / 3) % 3 - 1, i / 9 - 1))
+            .filter(|offset| *offset != IVec3::ZERO)
+            .filter_map(move |offset| {
+                let n = v + offset;
+                self.get_checked(n).map(|value| (n, value))
+            })
+    }
+
+    /// Creates an iterator over the cells a ray passes through, using the
+    /// voxel traversal of Amanatides and Woo. The ray starts at `origin` and
+    /// runs along `dir`, both given in world units on a grid with cells of
+    /// `grid_size`. A ray starting outside of this array is advanced to the
+    /// point where it enters the array, a ray that misses it yields nothing.
+    /// The iterator stops as soon as the ray leaves this array.
+    pub fn march(&self, origin : Vec3, dir : Vec3, grid_size : f32) -> impl Iterator<Item = IVec3> + '_ {
+        // Clip the ray against the bounds of this array with a slab test.
+        let bounds = self.dimensions().as_vec3() * grid_size;
+        let mut t_enter = 0.0f32;
+        let mut t_exit = f32::INFINITY;
+        for i in 0..3 {
+            if dir[i] == 0.0 {
+                if origin[i] < 0.0 || origin[i] >= bounds[i] {
+                    t_enter = f32::INFINITY;
+                }
+            } else {
+                let a = -origin[i] / dir[i];
+                let b = (bounds[i] - origin[i]) / dir[i];
+                t_enter = t_enter.max(a.min(b));
+                t_exit = t_exit.min(a.max(b));
+            }
+        }
+
+        let hits = !self.is_empty() && t_enter < t_exit;
+        let entry = if t_enter > 0.0 && hits { origin + dir * t_enter } else { origin };
+
+        // The entry point lies on the boundary, so keep its cell inside.
+        let max = self.dimensions() - IVec3::ONE;
+        let (cell, _) = map_to_grid_with_offset_vec3(entry, grid_size);
+        let mut cell = cell.max(IVec3::ZERO).min(max);
+        let offset = entry - cell.as_vec3() * grid_size;
+
+        // Per axis the step direction, the distance along the ray to the
+        // first cell boundary and the distance between two boundaries.
+        let axis = |d : f32, o : f32| -> (i32, f32, f32) {
+            if d > 0.0 {
+                (1, (grid_size - o) / d, grid_size / d)
+            } else if d < 0.0 {
+                (-1, o / -d, grid_size / -d)
+            } else {
+                (0, f32::INFINITY, f32::INFINITY)
+            }
+        };
+
+        let (sx, tx, dx) = axis(dir.x, offset.x);
+        let (sy, ty, dy) = axis(dir.y, offset.y);
+        let (sz, tz, dz) = axis(dir.z, offset.z);
+        let step = IVec3::new(sx, sy, sz);
+        let delta = Vec3::new(dx, dy, dz);
+        let mut t_max = Vec3::new(tx, ty, tz);
+        let mut inside = hits && self.contains_point(cell);
+
+        std::iter::from_fn(move || {
+            if !inside {
+                return None;
+            }
+
+            let current = cell;
+            if t_max.min_element().is_infinite() {
+                // A ray without direction never leaves its cell.
+                inside = false;
+                return Some(current);
+            }
+
+            if t_max.x < t_max.y && t_max.x < t_max.z {
+                cell.x += step.x;
+                t_max.x += delta.x;
+            } else if t_max.y < t_max.z {
+                cell.y += step.y;
+                t_max.y += delta.y;
+            } else {
+                cell.z += step.z;
+                t_max.z += delta.z;
+            }
+
+            inside = self.contains_point(cell);
+            Some(current)
+        })
+    }
+
     /// Creates a new immutable iterator.
     pub fn iter(&self) -> Array3dIter<'_, T> {
         Array3dIter {
@@ -185,163 +697,650 @@ impl<T: std::default::Default> Array3d<T> {
         }
     }
 
-    /// Creates a new mutable iterator.
-    fn iter_mut(&mut self) -> Array3dMutIter<'_, T> {
+    /// Creates a new mutable iterator. To simply update every cell prefer
+    /// `for_each_mut`, the iterator is meant for combining the cells with
+    /// other iterators.
+    pub fn iter_mut(&mut self) -> Array3dMutIter<'_, T> {
         let len = self.len();
 
         Array3dMutIter {
-            items: &mut self.array,
+            items: self.array.iter_mut(),
             cursor: 0,
             max: len,
             width: self.width,
             height: self.height,
         }
     }
-}
 
-impl<T: std::default::Default> Index<usize> for Array3d<T> {
-    type Output = T;
+    /// Creates a new parallel mutable iterator. Every cell is visited exactly
+    /// once along with its position.
+    #[cfg(feature = "rayon")]
+    pub fn par_iter_mut(&mut self) -> impl rayon::iter::ParallelIterator<Item = (IVec3, &mut T)>
+    where
+        T: Send,
+    {
+        use rayon::prelude::*;
 
-    fn index(&self, index: usize) -> &Self::Output {
-        assert!(index < self.len());
-        &self.array[index]
-    }
-}
+        let width = self.width;
+        let height = self.height;
 
-impl<T: std::default::Default> IndexMut<usize> for Array3d<T> {
-    fn index_mut(&mut self, index: usize) -> &mut T {
-        assert!(index < self.len());
-        &mut self.array[index]
+        self.array
+            .par_iter_mut()
+            .enumerate()
+            .map(move |(i, value)| (get_3d_from_1d_ivec3(width, height, i), value))
     }
-}
 
-pub struct Array3dIter<'a, T: std::default::Default> {
-    items: &'a Vec<T>,
-    cursor: usize,
-    max: usize,
-    width: usize,
-    height: usize,
-}
+    /// Creates a new parallel iterator over the planes of this array. Every
+    /// plane is stored contiguously, so it is handed out as a mutable slice
+    /// laid out row by row along with its depth `z`.
+    #[cfg(feature = "rayon")]
+    pub fn par_layers_mut(&mut self) -> impl rayon::iter::IndexedParallelIterator<Item = (i32, &mut [T])>
+    where
+        T: Send,
+    {
+        use rayon::prelude::*;
 
-impl<'a, T: std::default::Default> Iterator for Array3dIter<'a, T> {
-    type Item = (IVec3, &'a T);
+        let size = (self.width * self.height).max(1);
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let tmp = self.cursor;
-        if tmp >= self.max {
-            return None;
-        }
+        self.array
+            .par_chunks_mut(size)
+            .enumerate()
+            .map(|(z, layer)| (z as i32, layer))
+    }
 
-        self.cursor += 1;
-        let v = get_3d_from_1d_ivec3(self.width, self.height, tmp);
+    /// Creates a new immutable iterator that yields the flat index of every
+    /// cell along with its position and value.
+    pub fn iter_indexed(&self) -> impl Iterator<Item = (usize, IVec3, &T)> {
+        let width = self.width;
+        let height = self.height;
 
-        Some((v, &self.items[tmp]))
+        self.array
+            .iter()
+            .enumerate()
+            .map(move |(i, value)| (i, get_3d_from_1d_ivec3(width, height, i), value))
     }
-}
-
-impl<'a, T: std::default::Default> IntoIterator for &'a Array3d<T> {
-    type Item = (IVec3, &'a T);
 
-    type IntoIter = Array3dIter<'a, T>;
+    /// Creates a new immutable iterator that skips every cell holding the
+    /// default value.
+    pub fn iter_non_default(&self) -> impl Iterator<Item = (IVec3, &T)>
+    where
+        T: PartialEq + std::default::Default,
+    {
+        let default = T::default();
+        self.iter().filter(move |(_, value)| **value != default)
+    }
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter()
+    /// Creates an iterator over every valid position of this array in flat
+    /// order without borrowing the data.
+    pub fn positions(&self) -> impl Iterator<Item = IVec3> {
+        let width = self.width;
+        let height = self.height;
+        (0..self.len()).map(move |i| get_3d_from_1d_ivec3(width, height, i))
     }
-}
 
-pub struct Array3dMutIter<'a, T: std::default::Default> {
-    items: &'a mut Vec<T>,
-    cursor: usize,
-    max: usize,
-    width: usize,
-    height: usize,
-}
+    /// Creates an iterator over the plane at depth `z`. Since every plane is
+    /// stored contiguously this is a plain slice iteration. Panics if the
+    /// plane lies outside of this array.
+    pub fn slice_z(&self, z : i32) -> impl Iterator<Item = (IVec2, &T)> {
+        assert!(z >= 0 && (z as usize) < self.depth, "Invalid slice");
 
-impl<'a, T: std::default::Default> Iterator for Array3dMutIter<'a, T> {
-    type Item = (IVec3, &'a mut T);
+        let width = self.width;
+        let size = self.width * self.height;
+        let start = z as usize * size;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let tmp = self.cursor;
-        self.cursor += 1;
-        if tmp >= self.max {
-            return None;
-        }
+        self.array[start..start + size]
+            .iter()
+            .enumerate()
+            .map(move |(i, value)| (get_2d_from_1d_ivec2(width, i), value))
+    }
+
+    /// Creates a mutable iterator over the plane at depth `z`. Panics if the
+    /// plane lies outside of this array.
+    pub fn slice_z_mut(&mut self, z : i32) -> impl Iterator<Item = (IVec2, &mut T)> {
+        assert!(z >= 0 && (z as usize) < self.depth, "Invalid slice");
 
-        let v = get_3d_from_1d_ivec3(self.width, self.height, self.cursor);
+        let width = self.width;
+        let size = self.width * self.height;
+        let start = z as usize * size;
 
-        let pt = self.items.as_mut_ptr();
-        unsafe { Some((v, &mut *pt)) }
+        self.array[start..start + size]
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, value)| (get_2d_from_1d_ivec2(width, i), value))
     }
-}
 
-impl<'a, T: std::default::Default> IntoIterator for &'a mut Array3d<T> {
-    type Item = (IVec3, &'a mut T);
+    /// Copies the plane at depth `z` into a new 2d array. Panics if the plane
+    /// lies outside of this array.
+    pub fn layer(&self, z : i32) -> Array2d<T>
+    where
+        T: Clone,
+    {
+        Array2d::from_fn(self.width, self.height, |pos| self.get(pos.extend(z)).clone())
+    }
 
-    type IntoIter = Array3dMutIter<'a, T>;
+    /// Overwrite every cell with a clone of the given value.
+    pub fn fill(&mut self, value : T)
+    where
+        T: Clone,
+    {
+        self.array.fill(value);
+    }
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter_mut()
+    /// Overwrite every cell with the values returned by calling `f`
+    /// repeatedly.
+    pub fn fill_with(&mut self, f : impl FnMut() -> T) {
+        self.array.fill_with(f);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Overwrite every cell with the items of `data`, which is laid out slice
+    /// by slice and row by row. Panics if `data` does not hold exactly `len`
+    /// items.
+    pub fn copy_from(&mut self, data : &[T])
+    where
+        T: Copy,
+    {
+        assert_eq!(data.len(), self.len(), "Invalid data length");
+        self.array.copy_from_slice(data);
+    }
 
-    fn get_data_3d() -> Vec<(usize, usize, usize, usize, usize)> {
-        vec![
-            (4, 4, 0, 0, 0),
-            (4, 4, 1, 0, 0),
-            (4, 4, 1, 1, 0),
-            (4, 4, 1, 1, 1),
-            (4, 4, 2, 1, 0),
-            (4, 4, 2, 1, 1),
-            (4, 4, 2, 2, 1),
-            (4, 4, 2, 2, 2),
-            (4, 4, 3, 2, 1),
-            (4, 4, 3, 2, 2),
-            (4, 4, 3, 3, 2),
-            (4, 4, 3, 3, 3),
-        ]
+    /// Reset every cell to its default value. The allocated memory is kept.
+    pub fn clear(&mut self)
+    where
+        T: std::default::Default,
+    {
+        self.array.fill_with(T::default);
     }
 
-    #[test]
-    fn test_from_and_to_1d() {
-        let data = get_data_3d();
+    /// Keep every cell for which `pred` returns true and reset all other
+    /// cells to their default value.
+    pub fn retain(&mut self, pred : impl Fn(IVec3, &T) -> bool)
+    where
+        T: std::default::Default,
+    {
+        for (pos, value) in self.iter_mut() {
+            if !pred(pos, value) {
+                *value = T::default();
+            }
+        }
+    }
 
-        for (width, height, x1, y1, z1) in data {
-            let t = get_1d_from_3d(width, height, x1, y1, z1);
-            let (x2, y2, z2) = get_3d_from_1d(width, height, t);
+    /// Call `f` with the position and a mutable reference of every cell in
+    /// flat order.
+    pub fn apply(&mut self, mut f : impl FnMut(IVec3, &mut T)) {
+        let (width, height) = (self.width, self.height);
 
-            assert_eq!(x1, x2);
-            assert_eq!(y1, y2);
-            assert_eq!(z1, z2);
+        for (i, value) in self.array.iter_mut().enumerate() {
+            f(get_3d_from_1d_ivec3(width, height, i), value);
         }
     }
 
-    #[test]
-    fn test_from_and_to_1d_ivec() {
-        let data = get_data_3d();
+    /// Call `f` with the position and a mutable reference of every cell in
+    /// flat order. This is the preferred way to update every cell.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_flat_arrays::prelude::*;
+    /// let mut array : Array3d<i32> = Array3d::new(2, 2, 2);
+    /// array.for_each_mut(|pos, value| *value = pos.x);
+    /// assert_eq!(*array.get(IVec3 { x : 1, y : 0, z : 1 }), 1);
+    /// ```
+    pub fn for_each_mut(&mut self, f : impl FnMut(IVec3, &mut T)) {
+        self.apply(f);
+    }
 
-        for (width, height, x1, y1, z1) in data {
-            let v1 = IVec3 { x : x1 as i32, y : y1 as i32, z : z1 as i32 };
-            let t = get_1d_from_3d_ivec3(width, height, v1);
-            let v2 = get_3d_from_1d_ivec3(width, height, t);
+    /// Overwrite every cell with the value `f` returns for it. Unlike `map`
+    /// this reuses the memory of this array.
+    pub fn map_in_place(&mut self, mut f : impl FnMut(&T) -> T) {
+        self.apply(|_, value| *value = f(value));
+    }
 
-            assert_eq!(v1, v2);
+    /// Creates a new array of the same dimensions by applying `f` to every
+    /// cell of this array.
+    pub fn map<U>(&self, f : impl Fn(IVec3, &T) -> U) -> Array3d<U> {
+        Array3d {
+            width: self.width,
+            height: self.height,
+            depth: self.depth,
+            array: self.iter().map(|(pos, value)| f(pos, value)).collect(),
         }
     }
 
-    #[test]
-    fn test_into_iter() {
-        let test: Array3d<u64> = Array3d::new(2, 2, 2);
-        assert_eq!(test.len(), 8);
+    /// Copies the box `[min, min + size)` into a new array of the given size.
+    /// Returns an error if the region is empty, if `size` is negative or if
+    /// the region does not lie completely inside of this array.
+    pub fn sub_region(&self, min : IVec3, size : IVec3) -> Result<Array3d<T>, ArrayError<IVec3>>
+    where
+        T: Clone,
+    {
+        if size.x < 0 || size.y < 0 || size.z < 0 {
+            return Err(ArrayError::OutOfBounds { pos: min + size, dims: self.dimensions() });
+        }
 
-        for (_pos, value) in &test {
-            // Does this compile?
-            assert_eq!(*value, 0);
+        if size.x == 0 || size.y == 0 || size.z == 0 {
+            return Err(ArrayError::ZeroDimension);
         }
-    }
+
+        for corner in [min, min + size - IVec3::ONE] {
+            if self.checked_index(corner).is_none() {
+                return Err(ArrayError::OutOfBounds { pos: corner, dims: self.dimensions() });
+            }
+        }
+
+        Ok(Array3d::from_fn(size.x as usize, size.y as usize, size.z as usize, |pos| self.get(min + pos).clone()))
+    }
+
+    /// Copies `src` into this array with its origin placed at `dest`. Cells
+    /// that would land outside of this array are clipped.
+    pub fn blit(&mut self, dest : IVec3, src : &Array3d<T>)
+    where
+        T: Clone,
+    {
+        for (pos, value) in src {
+            if let Some(cell) = self.get_mut_checked(dest + pos) {
+                *cell = value.clone();
+            }
+        }
+    }
+
+    /// Returns the number of cells for which `pred` returns true.
+    pub fn count_where(&self, pred : impl Fn(&T) -> bool) -> usize {
+        self.array.iter().filter(|value| pred(value)).count()
+    }
+
+    /// Returns the position of the first cell in flat order for which `pred`
+    /// returns true.
+    pub fn find_position(&self, pred : impl Fn(&T) -> bool) -> Option<IVec3> {
+        self.array
+            .iter()
+            .position(pred)
+            .map(|i| get_3d_from_1d_ivec3(self.width, self.height, i))
+    }
+
+    /// Returns the smallest value of this array or None if it is empty.
+    pub fn min_value(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.array.iter().min()
+    }
+
+    /// Returns the largest value of this array or None if it is empty.
+    pub fn max_value(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.array.iter().max()
+    }
+
+    /// Returns the sum of all cells of this array.
+    pub fn sum(&self) -> T
+    where
+        T: Copy + std::iter::Sum<T>,
+    {
+        self.array.iter().copied().sum()
+    }
+
+    /// Returns the position of the first cell in flat order that holds the
+    /// largest value or None if this array is empty.
+    pub fn argmax(&self) -> Option<IVec3>
+    where
+        T: Ord,
+    {
+        self.iter()
+            .reduce(|best, item| if item.1 > best.1 { item } else { best })
+            .map(|(pos, _)| pos)
+    }
+
+    /// Returns the position of the first cell in flat order that holds the
+    /// smallest value or None if this array is empty.
+    pub fn argmin(&self) -> Option<IVec3>
+    where
+        T: Ord,
+    {
+        self.iter()
+            .reduce(|best, item| if item.1 < best.1 { item } else { best })
+            .map(|(pos, _)| pos)
+    }
+
+    /// Write `value` to every cell whose euclidean distance to `center` is at
+    /// most `radius`, i.e. `dx * dx + dy * dy + dz * dz <= radius * radius`.
+    /// A radius of one therefore covers the center and its six face
+    /// neighbors. Cells outside of this array are skipped.
+    pub fn fill_sphere(&mut self, center : IVec3, radius : i32, value : T)
+    where
+        T: Clone,
+    {
+        for z in -radius..=radius {
+            for y in -radius..=radius {
+                for x in -radius..=radius {
+                    let offset = IVec3::new(x, y, z);
+                    if offset.length_squared() > radius * radius {
+                        continue;
+                    }
+
+                    if let Some(cell) = self.get_mut_checked(center + offset) {
+                        *cell = value.clone();
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<T> Index<usize> for Array3d<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        assert!(index < self.len());
+        &self.array[index]
+    }
+}
+
+impl<T> IndexMut<usize> for Array3d<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        assert!(index < self.len());
+        &mut self.array[index]
+    }
+}
+
+impl<T> Index<IVec3> for Array3d<T> {
+    type Output = T;
+
+    fn index(&self, index: IVec3) -> &Self::Output {
+        self.get(index)
+    }
+}
+
+impl<T> IndexMut<IVec3> for Array3d<T> {
+    fn index_mut(&mut self, index: IVec3) -> &mut T {
+        self.get_mut(index)
+    }
+}
+
+impl<T: Clone> Clone for Array3d<T> {
+    fn clone(&self) -> Self {
+        Array3d {
+            width: self.width,
+            height: self.height,
+            depth: self.depth,
+            array: self.array.clone(),
+        }
+    }
+}
+
+/// Creates a 1x1x1 array holding a single default value.
+impl<T: std::default::Default> Default for Array3d<T> {
+    fn default() -> Self {
+        Array3d::new(1, 1, 1)
+    }
+}
+
+impl<T: PartialEq> PartialEq for Array3d<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && self.depth == other.depth
+            && self.array == other.array
+    }
+}
+
+impl<T: Eq> Eq for Array3d<T> {}
+
+/// Hashes the dimensions followed by the cells in flat order.
+impl<T: std::hash::Hash> std::hash::Hash for Array3d<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.width.hash(state);
+        self.height.hash(state);
+        self.depth.hash(state);
+        self.array.hash(state);
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Array3d<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Array3d {{ width: {}, height: {}, depth: {} }}",
+            self.width, self.height, self.depth
+        )?;
+
+        for (z, slice) in self.array.chunks((self.width * self.height).max(1)).enumerate() {
+            writeln!(f, "z = {}", z)?;
+
+            for row in slice.chunks(self.width.max(1)) {
+                writeln!(f, "{:?}", row)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for Array3d<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("Array3d", 4)?;
+        state.serialize_field("width", &self.width)?;
+        state.serialize_field("height", &self.height)?;
+        state.serialize_field("depth", &self.depth)?;
+        state.serialize_field("data", &self.array)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Array3d<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Array3d")]
+        struct Raw<T> {
+            width: usize,
+            height: usize,
+            depth: usize,
+            data: Vec<T>,
+        }
+
+        let raw = Raw::<T>::deserialize(deserializer)?;
+
+        Array3d::from_vec(raw.width, raw.height, raw.depth, raw.data).map_err(D::Error::custom)
+    }
+}
+
+pub struct Array3dIter<'a, T> {
+    items: &'a Vec<T>,
+    cursor: usize,
+    max: usize,
+    width: usize,
+    height: usize,
+}
+
+impl<'a, T> Iterator for Array3dIter<'a, T> {
+    type Item = (IVec3, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let tmp = self.cursor;
+        if tmp >= self.max {
+            return None;
+        }
+
+        self.cursor += 1;
+        let v = get_3d_from_1d_ivec3(self.width, self.height, tmp);
+
+        Some((v, &self.items[tmp]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.max - self.cursor;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Array3dIter<'_, T> {}
+
+impl<T> DoubleEndedIterator for Array3dIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.cursor >= self.max {
+            return None;
+        }
+
+        // max is the exclusive back cursor.
+        self.max -= 1;
+        let v = get_3d_from_1d_ivec3(self.width, self.height, self.max);
+
+        Some((v, &self.items[self.max]))
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Array3d<T> {
+    type Item = (IVec3, &'a T);
+
+    type IntoIter = Array3dIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub struct Array3dMutIter<'a, T> {
+    items: std::slice::IterMut<'a, T>,
+    cursor: usize,
+    max: usize,
+    width: usize,
+    height: usize,
+}
+
+impl<'a, T> Iterator for Array3dMutIter<'a, T> {
+    type Item = (IVec3, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let tmp = self.cursor;
+        if tmp >= self.max {
+            return None;
+        }
+
+        self.cursor += 1;
+        let v = get_3d_from_1d_ivec3(self.width, self.height, tmp);
+
+        self.items.next().map(|value| (v, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.max - self.cursor;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Array3dMutIter<'_, T> {}
+
+impl<'a, T> IntoIterator for &'a mut Array3d<T> {
+    type Item = (IVec3, &'a mut T);
+
+    type IntoIter = Array3dMutIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+pub struct Array3dIntoIter<T> {
+    items: std::iter::Enumerate<std::vec::IntoIter<T>>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Iterator for Array3dIntoIter<T> {
+    type Item = (IVec3, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.items.next().map(|(i, value)| (get_3d_from_1d_ivec3(self.width, self.height, i), value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.items.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for Array3dIntoIter<T> {}
+
+/// Consumes the array and yields every cell along with its position in flat
+/// order.
+impl<T> IntoIterator for Array3d<T> {
+    type Item = (IVec3, T);
+
+    type IntoIter = Array3dIntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Array3dIntoIter {
+            items: self.array.into_iter().enumerate(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_data_3d() -> Vec<(usize, usize, usize, usize, usize)> {
+        vec![
+            (4, 4, 0, 0, 0),
+            (4, 4, 1, 0, 0),
+            (4, 4, 1, 1, 0),
+            (4, 4, 1, 1, 1),
+            (4, 4, 2, 1, 0),
+            (4, 4, 2, 1, 1),
+            (4, 4, 2, 2, 1),
+            (4, 4, 2, 2, 2),
+            (4, 4, 3, 2, 1),
+            (4, 4, 3, 2, 2),
+            (4, 4, 3, 3, 2),
+            (4, 4, 3, 3, 3),
+        ]
+    }
+
+    #[test]
+    fn test_from_and_to_1d() {
+        let data = get_data_3d();
+
+        for (width, height, x1, y1, z1) in data {
+            let t = get_1d_from_3d(width, height, x1, y1, z1);
+            let (x2, y2, z2) = get_3d_from_1d(width, height, t);
+
+            assert_eq!(x1, x2);
+            assert_eq!(y1, y2);
+            assert_eq!(z1, z2);
+        }
+    }
+
+    #[test]
+    fn test_from_and_to_1d_ivec() {
+        let data = get_data_3d();
+
+        for (width, height, x1, y1, z1) in data {
+            let v1 = IVec3 { x : x1 as i32, y : y1 as i32, z : z1 as i32 };
+            let t = get_1d_from_3d_ivec3(width, height, v1);
+            let v2 = get_3d_from_1d_ivec3(width, height, t);
+
+            assert_eq!(v1, v2);
+        }
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let test: Array3d<u64> = Array3d::new(2, 2, 2);
+        assert_eq!(test.len(), 8);
+
+        for (_pos, value) in &test {
+            // Does this compile?
+            assert_eq!(*value, 0);
+        }
+    }
 
     #[test]
     fn test_into_iter_mut() {
@@ -390,4 +1389,998 @@ mod tests {
         test.set(pos, 64);
         assert_eq!(*test.get(pos), 64);
     }
+
+    #[test]
+    fn test_iter_mut_writes_distinct_cells() {
+        let mut test : Array3d<i32> = Array3d::new(3, 4, 2);
+
+        for (pos, value) in &mut test {
+            *value = pos.x + pos.y + pos.z * 10;
+        }
+
+        for x in 0..3 {
+            for y in 0..4 {
+                for z in 0..2 {
+                    let pos = IVec3 { x, y, z };
+                    assert_eq!(*test.get(pos), x + y + z * 10);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_iter_mut_method() {
+        let mut test : Array3d<i32> = Array3d::new(2, 2, 2);
+
+        for (pos, value) in test.iter_mut().filter(|(pos, _)| pos.x == 1) {
+            *value = pos.x + 1;
+        }
+
+        for (pos, value) in &test {
+            if pos.x == 1 {
+                assert_eq!(*value, 2);
+            } else {
+                assert_eq!(*value, 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_checked() {
+        let mut test : Array3d<usize> = Array3d::new(4, 4, 4);
+        test.set(IVec3 { x : 3, y : 2, z : 1 }, 7);
+
+        assert_eq!(test.get_checked(IVec3 { x : 3, y : 2, z : 1 }), Some(&7));
+        assert_eq!(test.get_checked(IVec3 { x : -1, y : 0, z : 0 }), None);
+        assert_eq!(test.get_checked(IVec3 { x : 0, y : -1, z : 0 }), None);
+        assert_eq!(test.get_checked(IVec3 { x : 0, y : 0, z : -1 }), None);
+        assert_eq!(test.get_checked(IVec3 { x : 4, y : 0, z : 0 }), None);
+        assert_eq!(test.get_checked(IVec3 { x : 0, y : 0, z : 4 }), None);
+    }
+
+    #[test]
+    fn test_get_mut_checked() {
+        let mut test : Array3d<usize> = Array3d::new(4, 4, 4);
+
+        *test.get_mut_checked(IVec3 { x : 1, y : 2, z : 3 }).unwrap() = 5;
+        assert_eq!(*test.get(IVec3 { x : 1, y : 2, z : 3 }), 5);
+
+        assert!(test.get_mut_checked(IVec3 { x : -1, y : 0, z : 0 }).is_none());
+        assert!(test.get_mut_checked(IVec3 { x : 0, y : -1, z : 0 }).is_none());
+        assert!(test.get_mut_checked(IVec3 { x : 4, y : 0, z : 0 }).is_none());
+    }
+
+    #[test]
+    fn test_clone() {
+        let mut test : Array3d<usize> = Array3d::new(3, 3, 3);
+        for i in 0..test.len() {
+            test[i] = i;
+        }
+
+        let copy = test.clone();
+        test.set(IVec3 { x : 1, y : 1, z : 1 }, 100);
+
+        assert_eq!(copy.len(), 27);
+        assert_eq!(*copy.get(IVec3 { x : 1, y : 1, z : 1 }), 13);
+        for i in 0..copy.len() {
+            assert_eq!(copy[i], i);
+        }
+    }
+
+    #[test]
+    fn test_debug() {
+        let mut test : Array3d<usize> = Array3d::new(3, 2, 2);
+        test.set(IVec3 { x : 2, y : 1, z : 1 }, 7);
+
+        let s = format!("{:?}", test);
+        assert!(s.contains("width: 3"));
+        assert!(s.contains("height: 2"));
+        assert!(s.contains("depth: 2"));
+        assert!(s.contains("z = 1"));
+        assert!(s.contains("[0, 0, 7]"));
+    }
+
+    #[test]
+    fn test_equality() {
+        let mut a : Array3d<usize> = Array3d::new(2, 2, 2);
+        let mut b : Array3d<usize> = Array3d::new(2, 2, 2);
+        assert_eq!(a, b);
+
+        a.set(IVec3 { x : 1, y : 0, z : 1 }, 1);
+        assert_ne!(a, b);
+
+        b.set(IVec3 { x : 1, y : 0, z : 1 }, 1);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_equality_swapped_dimensions() {
+        let a : Array3d<usize> = Array3d::new(2, 3, 1);
+        let b : Array3d<usize> = Array3d::new(3, 2, 1);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_from_vec() {
+        let test = Array3d::from_vec(2, 2, 2, (0..8).collect()).unwrap();
+        assert_eq!(test.len(), 8);
+        assert_eq!(*test.get(IVec3 { x : 1, y : 0, z : 0 }), 1);
+        assert_eq!(*test.get(IVec3 { x : 0, y : 1, z : 0 }), 2);
+        assert_eq!(*test.get(IVec3 { x : 0, y : 0, z : 1 }), 4);
+    }
+
+    #[test]
+    fn test_from_vec_size_mismatch() {
+        let r = Array3d::from_vec(2, 2, 2, vec![0; 9]);
+        assert_eq!(r.err(), Some(ArrayError::SizeMismatch { expected: 8, got: 9 }));
+    }
+
+    #[test]
+    fn test_from_fn() {
+        let test = Array3d::from_fn(4, 3, 2, |pos| (pos.x + 1) * (pos.y + 1) * (pos.z + 1));
+        assert_eq!(test.len(), 24);
+
+        assert_eq!(*test.get(IVec3 { x : 0, y : 0, z : 0 }), 1);
+        assert_eq!(*test.get(IVec3 { x : 3, y : 0, z : 0 }), 4);
+        assert_eq!(*test.get(IVec3 { x : 1, y : 2, z : 1 }), 12);
+        assert_eq!(*test.get(IVec3 { x : 3, y : 2, z : 1 }), 24);
+    }
+
+    #[test]
+    fn test_fill() {
+        let mut test : Array3d<usize> = Array3d::new(3, 2, 2);
+        test.fill(9);
+
+        for (_pos, value) in &test {
+            assert_eq!(*value, 9);
+        }
+    }
+
+    #[test]
+    fn test_fill_with() {
+        let mut test : Array3d<String> = Array3d::new(3, 2, 2);
+        test.fill_with(|| String::from("stone"));
+
+        for (_pos, value) in &test {
+            assert_eq!(value, "stone");
+        }
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut test : Array3d<usize> = Array3d::new(3, 2, 2);
+        test.fill(9);
+        test.clear();
+
+        assert_eq!(test.len(), 12);
+        for (_pos, value) in &test {
+            assert_eq!(*value, 0);
+        }
+    }
+
+    #[test]
+    fn test_index_ivec3() {
+        let mut test : Array3d<usize> = Array3d::new(4, 3, 2);
+
+        test[IVec3::new(2, 1, 1)] = 5;
+        assert_eq!(test[IVec3::new(2, 1, 1)], 5);
+        assert_eq!(test[get_1d_from_3d(4, 3, 2, 1, 1)], 5);
+
+        test[get_1d_from_3d(4, 3, 3, 2, 0)] = 8;
+        assert_eq!(test[IVec3::new(3, 2, 0)], 8);
+    }
+
+    #[test]
+    fn test_iter_indexed() {
+        let test : Array3d<usize> = Array3d::from_fn(3, 2, 2, |pos| get_1d_from_3d_ivec3(3, 2, pos));
+
+        let mut expected = 0;
+        for (i, pos, value) in test.iter_indexed() {
+            assert_eq!(i, expected);
+            assert_eq!(pos, get_3d_from_1d_ivec3(3, 2, i));
+            assert_eq!(*value, i);
+            expected += 1;
+        }
+
+        assert_eq!(expected, test.len());
+    }
+
+    #[test]
+    fn test_slice_z() {
+        let mut test : Array3d<i32> = Array3d::new(3, 2, 3);
+        for z in 0..3 {
+            for (_pos, value) in test.slice_z_mut(z) {
+                *value = z * 10;
+            }
+        }
+
+        let mut count = 0;
+        for (pos, value) in test.slice_z(1) {
+            assert_eq!(*value, 10);
+            assert_eq!(*test.get(pos.extend(1)), 10);
+            count += 1;
+        }
+        assert_eq!(count, 6);
+
+        let positions : Vec<IVec2> = test.slice_z(2).map(|(pos, _)| pos).collect();
+        assert_eq!(positions[0], IVec2::new(0, 0));
+        assert_eq!(positions[2], IVec2::new(2, 0));
+        assert_eq!(positions[3], IVec2::new(0, 1));
+        assert_eq!(*test.get(IVec3::new(2, 1, 0)), 0);
+    }
+
+    #[test]
+    fn test_neighbors6() {
+        let test : Array3d<usize> = Array3d::new(3, 3, 3);
+
+        assert_eq!(test.neighbors6(IVec3::new(0, 0, 0)).count(), 3);
+        assert_eq!(test.neighbors6(IVec3::new(1, 1, 1)).count(), 6);
+    }
+
+    #[test]
+    fn test_neighbors26() {
+        let test : Array3d<usize> = Array3d::new(3, 3, 3);
+
+        assert_eq!(test.neighbors26(IVec3::new(0, 0, 0)).count(), 7);
+
+        let center : Vec<IVec3> = test.neighbors26(IVec3::new(1, 1, 1)).map(|(pos, _)| pos).collect();
+        assert_eq!(center.len(), 26);
+        assert!(!center.contains(&IVec3::new(1, 1, 1)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let test = Array3d::from_fn(3, 2, 2, |pos| pos.x * 100 + pos.y * 10 + pos.z);
+
+        let json = serde_json::to_string(&test).unwrap();
+        let back : Array3d<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(test, back);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_size_mismatch() {
+        let json = r#"{"width":2,"height":2,"depth":2,"data":[1,2,3]}"#;
+        let r : Result<Array3d<i32>, _> = serde_json::from_str(json);
+        assert!(r.is_err());
+    }
+
+    #[test]
+    fn test_reflect() {
+        use bevy::reflect::ReflectRef;
+
+        let test : Array3d<u32> = Array3d::new(3, 2, 4);
+        let ReflectRef::Struct(reflected) = test.reflect_ref() else {
+            panic!("Array3d should reflect as a struct");
+        };
+
+        let depth = reflected.field("depth").unwrap().downcast_ref::<usize>();
+        assert_eq!(depth, Some(&4));
+    }
+
+    #[test]
+    fn test_component() {
+        #[derive(Resource, Default)]
+        struct Visited(usize);
+
+        fn visit(query : Query<&Array3d<u32>>, mut visited : ResMut<Visited>) {
+            for array in &query {
+                visited.0 += array.len();
+            }
+        }
+
+        let mut app = App::new();
+        app.init_resource::<Visited>();
+        app.add_systems(Update, visit);
+        app.world_mut().spawn(Array3d::<u32>::new(4, 4, 4));
+        app.update();
+
+        assert_eq!(app.world().resource::<Visited>().0, 64);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_iter_mut() {
+        use rayon::prelude::*;
+
+        let mut parallel = Array3d::from_fn(8, 4, 4, |pos| pos.x + pos.y * 8 + pos.z * 32);
+        let mut serial = parallel.clone();
+
+        parallel.par_iter_mut().for_each(|(_pos, value)| *value *= *value);
+        for (_pos, value) in serial.iter_mut() {
+            *value *= *value;
+        }
+
+        assert_eq!(parallel, serial);
+
+        parallel.par_iter_mut().for_each(|(pos, value)| *value = pos.x - pos.y + pos.z);
+        for (pos, value) in &parallel {
+            assert_eq!(*value, pos.x - pos.y + pos.z);
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_layers_mut() {
+        use rayon::prelude::*;
+
+        let mut test : Array3d<i32> = Array3d::new(3, 2, 4);
+        assert_eq!(test.par_layers_mut().len(), 4);
+
+        test.par_layers_mut().for_each(|(z, layer)| layer.fill(z));
+        for (pos, value) in &test {
+            assert_eq!(*value, pos.z);
+        }
+    }
+
+    #[test]
+    fn test_swap() {
+        let mut test : Array3d<usize> = Array3d::new(4, 3, 2);
+        let a = IVec3::new(0, 0, 0);
+        let b = IVec3::new(3, 2, 1);
+        test.set(a, 1);
+        test.set(b, 2);
+
+        test.swap(a, b);
+        assert_eq!(*test.get(a), 2);
+        assert_eq!(*test.get(b), 1);
+    }
+
+    #[test]
+    fn test_resize_preserving() {
+        let mut test = Array3d::from_fn(2, 2, 2, |pos| pos.x + pos.y * 10 + pos.z * 100 + 1);
+        test.resize_preserving(3, 3, 3);
+        assert_eq!(test.len(), 27);
+
+        for (pos, value) in &test {
+            if pos.x < 2 && pos.y < 2 && pos.z < 2 {
+                assert_eq!(*value, pos.x + pos.y * 10 + pos.z * 100 + 1);
+            } else {
+                assert_eq!(*value, 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_wrapped() {
+        let test = Array3d::from_fn(4, 3, 2, |pos| pos);
+
+        assert_eq!(*test.get_wrapped(IVec3::new(-1, -1, -1)), IVec3::new(3, 2, 1));
+        assert_eq!(*test.get_wrapped(IVec3::new(4, 3, 2)), IVec3::new(0, 0, 0));
+    }
+
+    #[test]
+    fn test_set_wrapped() {
+        let mut test : Array3d<usize> = Array3d::new(4, 3, 2);
+
+        test.set_wrapped(IVec3::new(-1, 0, 3), 1);
+        assert_eq!(*test.get(IVec3::new(3, 0, 1)), 1);
+
+        *test.get_wrapped_mut(IVec3::new(5, -1, -2)) = 2;
+        assert_eq!(*test.get(IVec3::new(1, 2, 0)), 2);
+    }
+
+    #[test]
+    fn test_get_clamped() {
+        let test = Array3d::from_fn(4, 4, 4, |pos| pos);
+
+        assert_eq!(*test.get_clamped(IVec3::new(-5, 100, 2)), IVec3::new(0, 3, 2));
+        assert_eq!(*test.get_clamped(IVec3::new(9, -1, -3)), IVec3::new(3, 0, 0));
+    }
+
+    #[test]
+    fn test_default() {
+        let test = Array3d::<u8>::default();
+        assert_eq!(test.len(), 1);
+        assert_eq!(test[0], 0);
+    }
+
+    #[test]
+    fn test_map() {
+        let test = Array3d::from_fn(3, 2, 2, |pos| get_1d_from_3d_ivec3(3, 2, pos));
+        let squares = test.map(|_pos, value| (value * value) as u64);
+
+        assert_eq!(squares.len(), 12);
+        for (pos, value) in &squares {
+            let i = get_1d_from_3d_ivec3(3, 2, pos) as u64;
+            assert_eq!(*value, i * i);
+        }
+    }
+
+    #[test]
+    fn test_positions() {
+        let test : Array3d<usize> = Array3d::new(2, 2, 2);
+        let positions : Vec<IVec3> = test.positions().collect();
+
+        assert_eq!(positions.len(), 8);
+        for (i, pos) in positions.iter().enumerate() {
+            assert_eq!(positions.iter().filter(|p| *p == pos).count(), 1);
+            assert_eq!(get_1d_from_3d_ivec3(2, 2, *pos), i);
+        }
+    }
+
+    #[test]
+    fn test_exact_size_iter() {
+        let mut test : Array3d<usize> = Array3d::new(3, 2, 2);
+
+        let mut iter = test.iter();
+        assert_eq!(iter.len(), test.len());
+        iter.next();
+        assert_eq!(iter.len(), 11);
+        iter.by_ref().for_each(drop);
+        assert_eq!(iter.len(), 0);
+
+        let mut iter_mut = test.iter_mut();
+        assert_eq!(iter_mut.size_hint(), (12, Some(12)));
+        iter_mut.next();
+        assert_eq!(iter_mut.len(), 11);
+    }
+
+    #[test]
+    fn test_reverse_iter() {
+        let test = Array3d::from_fn(3, 2, 2, |pos| pos.x * 100 + pos.y * 10 + pos.z);
+
+        let mut forward : Vec<(IVec3, &i32)> = test.iter().collect();
+        forward.reverse();
+        let backward : Vec<(IVec3, &i32)> = test.iter().rev().collect();
+        assert_eq!(forward, backward);
+        assert_eq!(backward[0].0, IVec3::new(2, 1, 1));
+    }
+
+    #[test]
+    fn test_sub_region() {
+        let test = Array3d::from_fn(4, 4, 4, |pos| pos);
+        let region = test.sub_region(IVec3::new(1, 2, 0), IVec3::new(2, 2, 2)).unwrap();
+
+        assert_eq!(region.len(), 8);
+        assert_eq!(*region.get(IVec3::new(0, 0, 0)), IVec3::new(1, 2, 0));
+        assert_eq!(*region.get(IVec3::new(1, 1, 1)), IVec3::new(2, 3, 1));
+    }
+
+    #[test]
+    fn test_sub_region_out_of_bounds() {
+        let test : Array3d<usize> = Array3d::new(4, 4, 4);
+
+        let r = test.sub_region(IVec3::new(3, 0, 0), IVec3::new(2, 2, 2));
+        assert_eq!(r.err(), Some(ArrayError::OutOfBounds { pos: IVec3::new(4, 1, 1), dims: IVec3::new(4, 4, 4) }));
+
+        let r = test.sub_region(IVec3::new(0, 0, 0), IVec3::new(2, 0, 2));
+        assert_eq!(r.err(), Some(ArrayError::ZeroDimension));
+    }
+
+    #[test]
+    fn test_blit() {
+        let mut test : Array3d<usize> = Array3d::new(3, 3, 3);
+        let stamp = Array3d::from_fn(2, 2, 2, |pos| get_1d_from_3d_ivec3(2, 2, pos) + 1);
+
+        test.blit(IVec3::new(2, 2, -1), &stamp);
+
+        for (pos, value) in &test {
+            if pos == IVec3::new(2, 2, 0) {
+                assert_eq!(*value, 5);
+            } else {
+                assert_eq!(*value, 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_count_where() {
+        let mut test : Array3d<usize> = Array3d::new(4, 4, 4);
+        test.set(IVec3::new(0, 0, 0), 1);
+        test.set(IVec3::new(3, 1, 2), 2);
+        test.set(IVec3::new(2, 3, 3), 3);
+
+        assert_eq!(test.count_where(|value| *value != 0), 3);
+        assert_eq!(test.count_where(|value| *value > 1), 2);
+    }
+
+    #[test]
+    fn test_find_position() {
+        let mut test : Array3d<usize> = Array3d::new(4, 3, 2);
+        test.set(IVec3::new(2, 1, 1), 42);
+
+        assert_eq!(test.find_position(|value| *value == 42), Some(IVec3::new(2, 1, 1)));
+    }
+
+    #[test]
+    fn test_find_position_none() {
+        let test : Array3d<usize> = Array3d::new(4, 3, 2);
+        assert_eq!(test.find_position(|value| *value == 42), None);
+    }
+
+    #[test]
+    fn test_contains_point() {
+        let test : Array3d<usize> = Array3d::new(5, 3, 2);
+
+        assert!(test.contains_point(IVec3::new(0, 0, 0)));
+        assert!(test.contains_point(IVec3::new(4, 2, 1)));
+        assert!(!test.contains_point(IVec3::new(-1, 0, 0)));
+        assert!(!test.contains_point(IVec3::new(0, 0, -1)));
+        assert!(!test.contains_point(IVec3::new(5, 0, 0)));
+        assert!(!test.contains_point(IVec3::new(0, 3, 0)));
+        assert!(!test.contains_point(IVec3::new(0, 0, 2)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_get_wrapping_x_panics() {
+        let test : Array3d<usize> = Array3d::new(5, 3, 2);
+        test.get(IVec3::new(5, 0, 0));
+    }
+
+    #[test]
+    fn test_dimensions() {
+        let test : Array3d<usize> = Array3d::new(5, 3, 2);
+        assert_eq!(test.width(), 5);
+        assert_eq!(test.height(), 3);
+        assert_eq!(test.depth(), 2);
+        assert_eq!(test.dimensions(), IVec3::new(5, 3, 2));
+    }
+
+    #[test]
+    fn test_from_iter_flat() {
+        let test = Array3d::from_iter_flat(2, 2, 2, 0..8).unwrap();
+        assert_eq!(*test.get(IVec3::new(1, 0, 0)), 1);
+        assert_eq!(*test.get(IVec3::new(0, 1, 0)), 2);
+        assert_eq!(*test.get(IVec3::new(1, 1, 1)), 7);
+    }
+
+    #[test]
+    fn test_from_iter_flat_size_mismatch() {
+        let too_few = Array3d::from_iter_flat(2, 2, 2, 0..3);
+        assert_eq!(too_few.err(), Some(ArrayError::SizeMismatch { expected: 8, got: 3 }));
+
+        let too_many = Array3d::from_iter_flat(2, 2, 2, 0..9);
+        assert_eq!(too_many.err(), Some(ArrayError::TooManyItems { expected: 8 }));
+
+        let unbounded = Array3d::from_iter_flat(2, 2, 2, std::iter::repeat(1));
+        assert_eq!(unbounded.err(), Some(ArrayError::TooManyItems { expected: 8 }));
+    }
+
+    #[test]
+    fn test_as_slice() {
+        let mut test = Array3d::from_fn(3, 2, 2, |pos| pos.x + pos.y * 10 + pos.z * 100);
+        assert_eq!(test.as_slice().len(), test.len());
+        assert_eq!(test.as_slice()[get_1d_from_3d(3, 2, 2, 1, 1)], 112);
+
+        test.as_mut_slice()[get_1d_from_3d(3, 2, 1, 1, 0)] = 99;
+        assert_eq!(*test.get(IVec3::new(1, 1, 0)), 99);
+    }
+
+    #[test]
+    fn test_into_vec() {
+        let data : Vec<usize> = (0..12).collect();
+        let test = Array3d::from_vec(3, 2, 2, data.clone()).unwrap();
+        assert_eq!(test.into_vec(), data);
+    }
+
+    #[test]
+    fn test_map_with_offset() {
+        let (cell, offset) = map_to_grid_with_offset_vec3(Vec3::new(96.0, 160.0, 40.0), 64.0);
+        assert_eq!(cell, IVec3::new(1, 2, 0));
+        assert_eq!(offset, Vec3::new(32.0, 32.0, 40.0));
+
+        let (cell, offset) = map_to_grid_with_offset_vec3(Vec3::new(-16.0, 8.0, 64.0), 64.0);
+        assert_eq!(cell, IVec3::new(-1, 0, 1));
+        assert_eq!(offset, Vec3::new(48.0, 8.0, 0.0));
+    }
+
+    #[test]
+    fn test_map_nonuniform() {
+        let cell = Vec3::new(64.0, 32.0, 16.0);
+
+        assert_eq!(map_to_grid_vec3_nonuniform(Vec3::new(63.0, 63.0, 63.0), cell), IVec3::new(0, 1, 3));
+        assert_eq!(map_to_grid_vec3_nonuniform(Vec3::new(64.0, 31.0, 16.0), cell), IVec3::new(1, 0, 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_map_nonuniform_zero_cell() {
+        map_to_grid_vec3_nonuniform(Vec3::ONE, Vec3::new(64.0, 32.0, 0.0));
+    }
+
+    #[test]
+    fn test_get_many_mut() {
+        let mut test : Array3d<usize> = Array3d::new(4, 4, 4);
+        let positions = [IVec3::new(0, 0, 0), IVec3::new(3, 1, 2), IVec3::new(2, 3, 3)];
+
+        let [a, b, c] = test.get_many_mut(positions).unwrap();
+        *a = 1;
+        *b = 2;
+        *c = *a + *b;
+
+        assert_eq!(*test.get(positions[0]), 1);
+        assert_eq!(*test.get(positions[1]), 2);
+        assert_eq!(*test.get(positions[2]), 3);
+    }
+
+    #[test]
+    fn test_get_many_mut_invalid() {
+        let mut test : Array3d<usize> = Array3d::new(4, 4, 4);
+
+        assert!(test.get_many_mut([IVec3::new(1, 1, 1), IVec3::new(1, 1, 1)]).is_none());
+        assert!(test.get_many_mut([IVec3::new(1, 1, 1), IVec3::new(1, 1, 4)]).is_none());
+    }
+
+    #[test]
+    fn test_try_set() {
+        let mut test : Array3d<usize> = Array3d::new(4, 3, 2);
+
+        assert_eq!(test.try_set(IVec3::new(3, 2, 1), 5), Ok(()));
+        assert_eq!(*test.get(IVec3::new(3, 2, 1)), 5);
+
+        let r = test.try_set(IVec3::new(0, 0, 2), 1);
+        assert_eq!(r, Err(ArrayError::OutOfBounds { pos: IVec3::new(0, 0, 2), dims: IVec3::new(4, 3, 2) }));
+    }
+
+    #[test]
+    fn test_from_layers() {
+        let layers : Vec<Array2d<i32>> = (0..3)
+            .map(|z| Array2d::from_fn(2, 2, |pos| pos.x + pos.y * 10 + z * 100))
+            .collect();
+
+        let test = Array3d::from_layers(layers.clone()).unwrap();
+        assert_eq!(test.dimensions(), IVec3::new(2, 2, 3));
+        assert_eq!(*test.get(IVec3::new(1, 1, 2)), 211);
+
+        let middle = test.layer(1);
+        assert_eq!(middle, layers[1]);
+        assert_eq!(*middle.get(IVec2::new(1, 0)), 101);
+    }
+
+    #[test]
+    fn test_from_layers_mismatch() {
+        let layers : Vec<Array2d<i32>> = vec![Array2d::new(2, 2), Array2d::new(2, 3)];
+
+        let r = Array3d::from_layers(layers);
+        assert_eq!(r.err(), Some(ArrayError::DimMismatch { expected: IVec2::new(2, 2), got: IVec2::new(2, 3) }));
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut array : Array3d<i32> = Array3d::new(3, 3, 3);
+        array.fill(7);
+        array.retain(|pos, _| pos.x == pos.y && pos.y == pos.z);
+
+        for (pos, value) in &array {
+            assert_eq!(*value, if pos.x == pos.y && pos.y == pos.z { 7 } else { 0 });
+        }
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut array : Array3d<i32> = Array3d::new(2, 2, 2);
+        array.apply(|pos, value| *value = pos.x * 100 + pos.y * 10 + pos.z);
+
+        for (pos, value) in &array {
+            assert_eq!(*value, pos.x * 100 + pos.y * 10 + pos.z);
+        }
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut array : Array3d<i32> = Array3d::new(0, 0, 0);
+        assert!(array.is_empty());
+        assert_eq!(array.len(), 0);
+        assert_eq!(array.iter().count(), 0);
+        assert_eq!(array.iter_mut().count(), 0);
+        assert_eq!(array.get_checked(IVec3::ZERO), None);
+        assert!(!format!("{:?}", array).is_empty());
+
+        array.resize(2, 2, 2);
+        assert!(!array.is_empty());
+        assert_eq!(array.len(), 8);
+    }
+
+    #[test]
+    fn test_shrink_to_fit() {
+        let mut array : Array3d<u8> = Array3d::new(16, 16, 16);
+        array.resize(2, 2, 2);
+
+        array.shrink_to_fit();
+        let capacity = array.into_vec().capacity();
+        assert!(capacity < 16 * 16 * 16);
+    }
+
+    #[test]
+    fn test_reserve() {
+        let mut array : Array3d<u8> = Array3d::new(2, 2, 2);
+        array.reserve(56);
+        assert_eq!(array.len(), 8);
+
+        let capacity = array.into_vec().capacity();
+        assert!(capacity >= 64);
+    }
+
+    #[test]
+    fn test_hash() {
+        use std::hash::{BuildHasher, RandomState};
+
+        let state = RandomState::new();
+        let a = Array3d::from_fn(2, 2, 2, |pos| pos.x + pos.y + pos.z);
+        let b = a.clone();
+        assert_eq!(state.hash_one(&a), state.hash_one(&b));
+
+        let c = Array3d::from_fn(2, 2, 2, |pos| pos.x);
+        assert_ne!(state.hash_one(&a), state.hash_one(&c));
+    }
+
+    #[test]
+    fn test_hash_set() {
+        use std::collections::HashSet;
+
+        let mut set = HashSet::new();
+        set.insert(Array3d::from_fn(2, 2, 2, |pos| pos.z));
+        set.insert(Array3d::from_fn(2, 2, 2, |pos| pos.z));
+
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_iter_mut_every_cell() {
+        let mut test : Array3d<i32> = Array3d::new(2, 3, 2);
+        let refs : Vec<(IVec3, &mut i32)> = test.iter_mut().collect();
+        assert_eq!(refs.len(), 12);
+
+        for (pos, value) in refs {
+            *value = pos.z * 6 + pos.y * 2 + pos.x;
+        }
+
+        assert_eq!(test.into_vec(), (0..12).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_iter_non_default() {
+        let mut test : Array3d<i32> = Array3d::new(3, 3, 3);
+        test.set(IVec3::new(1, 2, 0), 5);
+        test.set(IVec3::new(0, 0, 2), 7);
+
+        let cells : Vec<(IVec3, &i32)> = test.iter_non_default().collect();
+        assert_eq!(cells, vec![(IVec3::new(1, 2, 0), &5), (IVec3::new(0, 0, 2), &7)]);
+    }
+
+    #[test]
+    fn test_centered() {
+        let test = Array3d::from_fn(5, 5, 3, |pos| pos);
+        assert_eq!(test.center(), IVec3::new(2, 2, 1));
+        assert_eq!(*test.get_centered(IVec3::ZERO), IVec3::new(2, 2, 1));
+        assert_eq!(*test.get_centered(IVec3::new(1, -2, 1)), IVec3::new(3, 0, 2));
+    }
+
+    #[test]
+    fn test_reductions() {
+        let test = Array3d::from_fn(2, 2, 2, |pos| pos.x + pos.y * 2 + pos.z * 4 - 3);
+        assert_eq!(test.min_value(), Some(&-3));
+        assert_eq!(test.max_value(), Some(&4));
+        assert_eq!(test.sum(), 4);
+    }
+
+    #[test]
+    fn test_argmax_argmin() {
+        let mut test : Array3d<i32> = Array3d::new(3, 3, 3);
+        test.set(IVec3::new(1, 0, 2), 9);
+        test.set(IVec3::new(2, 2, 0), -4);
+
+        assert_eq!(test.argmax(), Some(IVec3::new(1, 0, 2)));
+        assert_eq!(test.argmin(), Some(IVec3::new(2, 2, 0)));
+    }
+
+    #[test]
+    fn test_get_flat() {
+        let mut test : Array3d<i32> = Array3d::new(2, 2, 2);
+        *test.get_flat_mut(7).unwrap() = 5;
+
+        assert_eq!(*test.get(IVec3::new(1, 1, 1)), 5);
+        assert_eq!(test.get_flat(7), Some(&5));
+        assert_eq!(test.get_flat(8), None);
+        assert_eq!(test.get_flat_mut(8), None);
+    }
+
+    #[test]
+    fn test_index_of() {
+        let test = Array3d::from_fn(4, 3, 2, |pos| pos);
+        for i in 0..test.len() {
+            let pos = test.position_of(i);
+            assert_eq!(test.index_of(pos), i);
+            assert_eq!(test[i], pos);
+        }
+    }
+
+    #[test]
+    fn test_into_iter_owned() {
+        let test = Array3d::from_fn(2, 1, 2, |pos| pos.x + pos.z * 2);
+        let cells : Vec<(IVec3, i32)> = test.into_iter().collect();
+
+        assert_eq!(cells, vec![
+            (IVec3::new(0, 0, 0), 0),
+            (IVec3::new(1, 0, 0), 1),
+            (IVec3::new(0, 0, 1), 2),
+            (IVec3::new(1, 0, 1), 3),
+        ]);
+    }
+
+    #[test]
+    fn test_for_each_mut() {
+        let mut test : Array3d<i32> = Array3d::new(2, 3, 2);
+        test.for_each_mut(|pos, value| *value = pos.z * 6 + pos.y * 2 + pos.x);
+
+        assert_eq!(test.into_vec(), (0..12).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_fill_sphere() {
+        let mut test : Array3d<bool> = Array3d::new(5, 5, 5);
+        let center = IVec3::splat(2);
+        test.fill_sphere(center, 1, true);
+
+        assert_eq!(test.count_where(|value| *value), 7);
+        for (pos, value) in &test {
+            assert_eq!(*value, (pos - center).abs().element_sum() <= 1);
+        }
+
+        // Spheres reaching past the edges are clipped.
+        let mut test : Array3d<bool> = Array3d::new(5, 5, 5);
+        test.fill_sphere(IVec3::ZERO, 1, true);
+        assert_eq!(test.count_where(|value| *value), 4);
+    }
+
+    #[test]
+    fn test_march() {
+        let test : Array3d<u8> = Array3d::new(4, 4, 4);
+
+        let cells : Vec<IVec3> = test.march(Vec3::new(2.0, 5.0, 9.0), Vec3::X, 4.0).collect();
+        assert_eq!(cells, (0..4).map(|x| IVec3::new(x, 1, 2)).collect::<Vec<_>>());
+
+        let cells : Vec<IVec3> = test.march(Vec3::new(14.0, 2.0, 2.0), Vec3::NEG_X, 4.0).collect();
+        assert_eq!(cells, (0..4).rev().map(|x| IVec3::new(x, 0, 0)).collect::<Vec<_>>());
+
+        // A diagonal ray moves through face adjacent cells only.
+        let cells : Vec<IVec3> = test.march(Vec3::new(1.0, 2.0, 3.0), Vec3::new(1.0, 0.7, 0.3), 4.0).collect();
+        assert_eq!(cells[0], IVec3::new(0, 0, 0));
+        assert_eq!(*cells.last().unwrap(), IVec3::new(3, 3, 1));
+        assert_eq!(cells.len(), 8);
+        for step in cells.windows(2) {
+            assert_eq!((step[1] - step[0]).abs().element_sum(), 1);
+        }
+
+        assert_eq!(test.march(Vec3::new(-1.0, 2.0, 2.0), Vec3::NEG_X, 4.0).count(), 0);
+        assert_eq!(test.march(Vec3::new(-1.0, 20.0, 2.0), Vec3::X, 4.0).count(), 0);
+        assert_eq!(test.march(Vec3::new(1.0, 2.0, 2.0), Vec3::ZERO, 4.0).count(), 1);
+    }
+
+    #[test]
+    fn test_grid_cell_center() {
+        assert_eq!(grid_cell_center_vec3(IVec3::new(1, 1, 1), 64.0), Vec3::splat(96.0));
+        assert_eq!(grid_cell_center_vec3(IVec3::new(0, -1, 2), 2.0), Vec3::new(1.0, -1.0, 5.0));
+    }
+
+    // The side length is chosen relative to the pointer width, so the number
+    // of cells overflows on 32 bit targets as well as on 64 bit targets.
+    #[test]
+    #[should_panic(expected = "overflow")]
+    fn test_new_overflow() {
+        let side = 1 << (usize::BITS / 3 + 1);
+        let _ : Array3d<u8> = Array3d::new(side, side, side);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow")]
+    fn test_resize_overflow() {
+        let side = 1 << (usize::BITS / 3 + 1);
+        let mut test : Array3d<u8> = Array3d::new(1, 1, 1);
+        test.resize(side, side, side);
+    }
+
+    #[test]
+    fn test_try_get_1d_from_3d() {
+        let side = 1 << (usize::BITS / 3 + 1);
+        assert_eq!(try_get_1d_from_3d(2, 3, 1, 2, 3), Some(get_1d_from_3d(2, 3, 1, 2, 3)));
+        assert_eq!(try_get_1d_from_3d(side, side, 0, 0, side), None);
+    }
+
+    #[test]
+    fn test_without_default() {
+        #[derive(Debug, PartialEq)]
+        struct Voxel(u8);
+
+        let test = Array3d::from_fn(2, 2, 2, |pos| Voxel(pos.z as u8));
+        assert_eq!(*test.get(IVec3::new(1, 1, 1)), Voxel(1));
+        assert_eq!(test.iter().filter(|(_, value)| value.0 == 0).count(), 4);
+    }
+
+    #[test]
+    fn test_map_in_place() {
+        let mut test = Array3d::from_fn(2, 1, 2, |pos| pos.x + pos.z * 2);
+        test.map_in_place(|value| value * 2);
+
+        assert_eq!(test.dimensions(), IVec3::new(2, 1, 2));
+        assert_eq!(test.as_slice(), &[0, 2, 4, 6]);
+    }
+
+    #[test]
+    fn test_copy_from() {
+        let data : Vec<i32> = (0..8).collect();
+
+        let mut test : Array3d<i32> = Array3d::new(2, 2, 2);
+        test.copy_from(&data);
+        assert_eq!(*test.get(IVec3::new(1, 0, 0)), 1);
+        assert_eq!(*test.get(IVec3::new(0, 1, 1)), 6);
+    }
+
+    #[test]
+    fn test_array_error_variants() {
+        let r : Result<Array3d<i32>, _> = Array3d::from_layers(Vec::new());
+        assert!(matches!(r, Err(ArrayError::ZeroDimension)));
+
+        let r = Array3d::from_vec(2, 2, 2, vec![0; 7]);
+        assert!(matches!(r, Err(ArrayError::SizeMismatch { expected: 8, got: 7 })));
+
+        let mut test : Array3d<i32> = Array3d::new(2, 2, 2);
+        match test.try_set(IVec3::new(2, 0, 0), 1) {
+            Err(ArrayError::OutOfBounds { pos, dims }) => {
+                assert_eq!(pos, IVec3::new(2, 0, 0));
+                assert_eq!(dims, IVec3::new(2, 2, 2));
+            }
+            r => panic!("unexpected result {:?}", r),
+        }
+
+        let layers = vec![Array2d::<i32>::new(2, 2), Array2d::new(3, 2)];
+        let r = Array3d::from_layers(layers);
+        assert!(matches!(r, Err(ArrayError::DimMismatch { .. })));
+
+        let e : ArrayError<IVec3> = ArrayError::ZeroDimension;
+        assert_eq!(e.to_string(), "array dimensions must not be zero");
+    }
+
+    #[test]
+    fn test_resize_with_value() {
+        let mut test : Array3d<i32> = Array3d::new(2, 2, 1);
+        test.set(IVec3::new(1, 1, 0), 5);
+        test.resize_with_value(3, 3, 2, 1);
+
+        assert_eq!(*test.get(IVec3::new(1, 1, 0)), 5);
+        assert_eq!(*test.get(IVec3::new(0, 0, 0)), 0);
+        assert_eq!(*test.get(IVec3::new(2, 1, 0)), 1);
+        assert_eq!(*test.get(IVec3::new(0, 0, 1)), 1);
+        assert_eq!(test.iter().filter(|(_, v)| **v == 1).count(), 18 - 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "Array is empty")]
+    fn test_get_wrapped_empty() {
+        let test : Array3d<i32> = Array3d::new(2, 0, 2);
+        test.get_wrapped(IVec3::new(1, 1, 1));
+    }
+
+    #[test]
+    fn test_march_from_outside() {
+        let mut test : Array3d<u8> = Array3d::new(4, 4, 4);
+        test.set(IVec3::new(2, 1, 3), 1);
+
+        // A camera in front of the volume looking along +x.
+        let hit = test
+            .march(Vec3::new(-10.0, 6.0, 13.0), Vec3::X, 4.0)
+            .find(|cell| *test.get(*cell) != 0);
+        assert_eq!(hit, Some(IVec3::new(2, 1, 3)));
+
+        // Entering through the far side while looking back.
+        let cells : Vec<IVec3> = test.march(Vec3::new(30.0, 2.0, 2.0), Vec3::NEG_X, 4.0).collect();
+        assert_eq!(cells, (0..4).rev().map(|x| IVec3::new(x, 0, 0)).collect::<Vec<_>>());
+
+        // A diagonal ray entering through a corner region.
+        let cells : Vec<IVec3> = test.march(Vec3::new(-4.0, -4.0, 1.0), Vec3::new(1.0, 1.0, 0.0), 4.0).collect();
+        assert_eq!(cells[0], IVec3::new(0, 0, 0));
+        assert!(cells.iter().all(|cell| test.contains_point(*cell)));
+    }
+
+    #[test]
+    fn test_from_vec_overflow() {
+        let side = 1 << (usize::BITS / 3 + 1);
+        let r : Result<Array3d<u8>, _> = Array3d::from_vec(side, side, side, Vec::new());
+        assert_eq!(r, Err(ArrayError::DimensionOverflow));
+        let r : Result<Array3d<u8>, _> = Array3d::from_iter_flat(side, side, side, std::iter::empty());
+        assert_eq!(r, Err(ArrayError::DimensionOverflow));
+    }
 }
@@ -1,3 +1,5 @@
+mod dimensions;
+mod error;
 mod flat_array_2d;
 mod flat_array_3d;
 
@@ -11,22 +13,34 @@ pub mod prelude {
         // 2d
         pub use flat_array_2d::get_1d_from_2d;
         pub use flat_array_2d::get_1d_from_2d_ivec2;
+        pub use flat_array_2d::try_get_1d_from_2d;
         pub use flat_array_2d::get_2d_from_1d;
         pub use flat_array_2d::get_2d_from_1d_ivec2;
         pub use flat_array_2d::quantize_to_grid;
+        pub use flat_array_2d::grid_cell_center;
         pub use flat_array_2d::map_to_grid_vec2;
+        pub use flat_array_2d::map_to_grid_bounded;
+        pub use flat_array_2d::map_to_grid_vec2_nonuniform;
+        pub use flat_array_2d::map_to_grid_vec2_offset;
+        pub use flat_array_2d::map_to_grid_with_offset;
 
         // 3d
         pub use flat_array_3d::get_1d_from_3d;
         pub use flat_array_3d::get_1d_from_3d_ivec3;
+        pub use flat_array_3d::try_get_1d_from_3d;
         pub use flat_array_3d::get_3d_from_1d;
         pub use flat_array_3d::get_3d_from_1d_ivec3;
         pub use flat_array_3d::map_to_grid_vec3;
+        pub use flat_array_3d::map_to_grid_vec3_nonuniform;
+        pub use flat_array_3d::map_to_grid_with_offset_vec3;
+        pub use flat_array_3d::grid_cell_center_vec3;
     }
 
+    use crate::error;
     use crate::flat_array_2d;
     use crate::flat_array_3d;
 
+    pub use error::ArrayError;
     pub use flat_array_2d::Array2d;
     pub use flat_array_3d::Array3d;
 }